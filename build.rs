@@ -0,0 +1,133 @@
+//! Generates the registry of solutions that the main binary runs in-process.
+//!
+//! Every `src/bin/<year>-<day>.rs` is compiled into the main binary as a module, and its `SOLUTION`
+//! (see the `solution!` macro) is listed in `SOLUTIONS`. The per-day binaries are unaffected.
+//!
+//! With the `embed_inputs` feature, the inputs and examples of every solution are listed as well,
+//! see `template::embedded`.
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let mut days: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    // the year may be prefixed with the lowercase prefix of its event, e.g. `ec2024`.
+                    let (year, day) = name.strip_suffix(".rs")?.split_once('-')?;
+                    let (prefix, number) = year.split_at(year.len().checked_sub(4)?);
+                    let is_year = prefix.bytes().all(|b| b.is_ascii_lowercase())
+                        && number.bytes().all(|b| b.is_ascii_digit());
+                    let is_day = day.len() == 2 && day.parse::<u8>().is_ok_and(|day| day > 0);
+                    (is_year && is_day).then(|| (year.to_string(), day.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort();
+
+    let mut registry = String::new();
+
+    for (year, day) in &days {
+        let path = bin_dir.join(format!("{year}-{day}.rs"));
+        registry.push_str(&format!(
+            "#[cfg(not(test))]\n\
+             #[path = {path:?}]\n\
+             #[allow(dead_code, unused, clippy::all, clippy::pedantic)]\n\
+             mod y{year}_d{day};\n\n",
+            path = path.display().to_string(),
+        ));
+    }
+
+    // the day modules are left out of test builds, their tests run with their own binaries.
+    registry.push_str(&format!(
+        "#[cfg(not(test))]\n\
+         pub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[{}];\n\n\
+         #[cfg(test)]\n\
+         pub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[];\n",
+        days.iter()
+            .map(|(year, day)| format!("y{year}_d{day}::SOLUTION"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), registry).unwrap();
+
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some() {
+        let data_dir = Path::new(&manifest_dir).join("data");
+        println!("cargo:rerun-if-changed={}", data_dir.display());
+
+        let embedded = embedded_files(&data_dir, &days);
+        fs::write(Path::new(&out_dir).join("embedded.rs"), embedded).unwrap();
+    }
+}
+
+/// Lists the inputs and examples of each day as `(prefix, year, day, files)`, where each file is
+/// embedded with `include_str!` under its path relative to `data/<year>`. Missing files are left
+/// out.
+fn embedded_files(data_dir: &Path, days: &[(String, String)]) -> String {
+    let mut table = String::from("&[\n");
+
+    for (year, day) in days {
+        let year_dir = data_dir.join(year);
+        let mut files: Vec<PathBuf> = [
+            year_dir.join("inputs").join(format!("{day}.txt")),
+            year_dir.join("examples").join(format!("{day}.txt")),
+        ]
+        .into_iter()
+        .filter(|path| path.is_file())
+        .collect();
+
+        if let Ok(entries) = fs::read_dir(year_dir.join("examples").join(day)) {
+            let mut examples: Vec<PathBuf> = entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.is_file()
+                        && path
+                            .extension()
+                            .is_some_and(|ext| ext == "txt" || ext == "answers")
+                })
+                .collect();
+            examples.sort();
+            files.append(&mut examples);
+        }
+
+        if files.is_empty() {
+            continue;
+        }
+
+        let (prefix, number) = year.split_at(year.len() - 4);
+        let day_number: u8 = day.parse().unwrap();
+        table.push_str(&format!("    ({prefix:?}, {number}, {day_number}, &[\n"));
+
+        for path in files {
+            let relative = path.strip_prefix(&year_dir).unwrap();
+            let relative: Vec<String> = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            table.push_str(&format!(
+                "        ({:?}, include_str!({:?})),\n",
+                relative.join("/"),
+                path.display().to_string()
+            ));
+        }
+
+        table.push_str("    ]),\n");
+    }
+
+    table.push(']');
+    table
+}
@@ -75,20 +75,7 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(res)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        println!("{:?}", result);
-        assert_eq!(result, Some(142));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(281));
-    }
+advent_of_code::solution_tests! {
+    part_one: Some(142),
+    part_two: Some(281),
 }
@@ -2,10 +2,10 @@ use std::{cmp::max, str::FromStr};
 
 use advent_of_code::ParseError;
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, parse = parse_games);
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     id: u32,
     draws: Vec<Draw>,
 }
@@ -14,16 +14,14 @@ impl FromStr for Game {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (gs, rs) = s.split_once(':').ok_or(ParseError)?;
+        let (gs, rs) = s.split_once(':').ok_or("missing `:` after game id")?;
         let id = gs
             .strip_prefix("Game ")
-            .ok_or(ParseError)
-            .and_then(|s| s.parse::<u32>().map_err(|_| ParseError))?;
+            .ok_or("missing `Game ` prefix")?
+            .parse::<u32>()?;
 
-        let draws: Result<Vec<Draw>, ParseError> = rs
-            .split(';')
-            .map(|s| s.trim().parse().map_err(|_| ParseError))
-            .collect();
+        let draws: Result<Vec<Draw>, ParseError> =
+            rs.split(';').map(|s| s.trim().parse()).collect();
         // TODO: without type annotation and ? in let?
         Ok(Game { id, draws: draws? })
     }
@@ -46,26 +44,26 @@ impl FromStr for Draw {
         let mut green = 0;
         let mut blue = 0;
         for part in parts {
-            let (left, right) = part.split_once(' ').ok_or(ParseError)?;
-            let value = left.parse().map_err(|_| ParseError)?;
+            let (left, right) = part.split_once(' ').ok_or("missing space in draw")?;
+            let value = left.parse()?;
             match right {
                 "red" => red = value,
                 "green" => green = value,
                 "blue" => blue = value,
-                _ => return Err(ParseError),
+                _ => return Err(ParseError::new(format!("unknown color `{right}`"))),
             }
         }
         return Ok(Draw { red, green, blue });
     }
 }
 
-fn parse_games(input: &str) -> Vec<Game> {
+pub fn parse_games(input: &str) -> Vec<Game> {
     input.lines().map(|l| l.parse::<Game>()).flatten().collect()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let sum = parse_games(input)
-        .into_iter()
+pub fn part_one(games: &[Game]) -> Option<u32> {
+    let sum = games
+        .iter()
         .filter(|g| {
             g.draws
                 .iter()
@@ -82,8 +80,8 @@ fn min_cubes(game: &Game) -> (u32, u32, u32) {
     })
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let sum = parse_games(input)
+pub fn part_two(games: &[Game]) -> Option<u32> {
+    let sum = games
         .iter()
         .map(|game| {
             let (r, g, b) = min_cubes(game);
@@ -93,19 +91,8 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(sum)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(8));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
+advent_of_code::solution_tests! {
+    parse = parse_games,
+    part_one: Some(8),
+    part_two: None,
 }
@@ -67,7 +67,7 @@ fn parse_plan(input: &str) -> Result<Plan, ParseError> {
                     Some((start, ref mut content)) => {
                         content.push(c);
                         if x + 1 >= r.len() {
-                            let number = content.parse().map_err(|_| ParseError)?;
+                            let number = content.parse()?;
                             parts.push(Part {
                                 number,
                                 row: y,
@@ -84,7 +84,7 @@ fn parse_plan(input: &str) -> Result<Plan, ParseError> {
             // FIXME: the fact that we are identifying parts twice is hacky
             match part_cur {
                 Some((start, ref mut content)) => {
-                    let number = content.parse().map_err(|_| ParseError)?;
+                    let number = content.parse()?;
                     parts.push(Part {
                         number,
                         row: y,
@@ -109,41 +109,29 @@ fn parse_plan(input: &str) -> Result<Plan, ParseError> {
     Ok(Plan { parts, symbols })
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let plan = parse_plan(input).ok()?;
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let plan = parse_plan(input)?;
     let parts: Vec<Part> = plan
         .parts
         .into_iter()
         .filter(|p| p.is_adjacent_to_symbol(&plan.symbols))
         .collect();
     let sum = parts.iter().map(|p| p.number).sum();
-    Some(sum)
+    Ok(sum)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let plan = parse_plan(input).ok()?;
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
+    let plan = parse_plan(input)?;
     let gear_ratios: Vec<u32> = plan
         .symbols
         .into_iter()
         .map(|s| s.gear_ratio(&plan.parts))
         .flatten()
         .collect();
-    Some(gear_ratios.iter().sum())
+    Ok(gear_ratios.iter().sum())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4361));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(467835));
-    }
+advent_of_code::solution_tests! {
+    part_one: Ok(4361),
+    part_two: Ok(467835),
 }
@@ -66,19 +66,7 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(cardstack.iter().map(|(_, count)| count).sum())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(13));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(30));
-    }
+advent_of_code::solution_tests! {
+    part_one: Some(13),
+    part_two: Some(30),
 }
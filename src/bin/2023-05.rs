@@ -1,3 +1,5 @@
+use advent_of_code::template::progress::Progress;
+
 advent_of_code::solution!(5);
 
 fn map(dst_start: u32, src_start: u32, len: u32) -> impl Fn(u32) -> Option<u32> {
@@ -66,7 +68,7 @@ pub fn part_two(input: &str) -> Option<u32> {
             seeds.push(i);
         }
     }
-    let total = seeds.len();
+    let mut progress = Progress::new(seeds.len());
 
     let full_map = compose_all(
         rest.split("\n\n")
@@ -76,29 +78,14 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     seeds
         .iter()
-        .enumerate()
-        .map(|(ix, x)| {
-            if ix % 1000000 == 0 {
-                println!("{ix}/{total}");
-            }
+        .map(|x| {
+            progress.inc(1);
             full_map(*x)
         })
         .min()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(35));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(46));
-    }
+advent_of_code::solution_tests! {
+    part_one: Some(35),
+    part_two: Some(46),
 }
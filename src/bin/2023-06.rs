@@ -41,7 +41,7 @@ fn travel_distance(holding_time: u64, duration: u64) -> u64 {
 
 pub fn part_one(input: &str) -> Option<u64> {
     let games = parse_games(input);
-    println!("{games:?}");
+    advent_of_code::trace!("{games:?}");
     Some(
         games
             .iter()
@@ -77,23 +77,11 @@ fn parse_game(input: &str) -> Game {
 
 pub fn part_two(input: &str) -> Option<u64> {
     let game = parse_game(input);
-    println!("{game:?}");
+    advent_of_code::trace!("{game:?}");
     Some(winning_times(&game).len().try_into().expect("truncated"))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(288));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(71503));
-    }
+advent_of_code::solution_tests! {
+    part_one: Some(288),
+    part_two: Some(71503),
 }
@@ -133,22 +133,15 @@ pub fn part_two(input: &str) -> Option<u32> {
     )
 }
 
+advent_of_code::solution_tests! {
+    part_one: Some(6440),
+    part_two: Some(5905),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6440));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(5905));
-    }
-
     #[test]
     fn test_get_type() {
         assert_eq!(
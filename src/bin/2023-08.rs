@@ -1,23 +1,23 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(8, parse = parse);
 
 #[derive(Debug)]
-enum Step {
+pub enum Step {
     R,
     L,
 }
 
-type Instructions = Vec<Step>;
+pub type Instructions = Vec<Step>;
 
 type Node = String;
 
 #[derive(Debug)]
-struct Network {
+pub struct Network {
     nodes: HashMap<Node, (Node, Node)>,
 }
 
-fn parse(input: &str) -> (Instructions, Network) {
+pub fn parse(input: &str) -> (Instructions, Network) {
     let (instructions, network) = input.split_once("\n\n").unwrap();
     let instructions = instructions
         .chars()
@@ -51,15 +51,14 @@ fn parse_node(s: &str) -> (Node, (Node, Node)) {
     (n.trim().to_string(), (l.to_string(), r.to_string()))
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (ins, network) = parse(input);
-    println!("{ins:?}");
-    println!("{network:?}");
+pub fn part_one((ins, network): &(Instructions, Network)) -> Option<u32> {
+    advent_of_code::trace!(2; "{ins:?}");
+    advent_of_code::trace!(2; "{network:?}");
     let mut it = ins.iter().cycle();
     let mut count = 0;
     let mut cur = &String::from("AAA");
     while let Some(step) = it.next() {
-        let next = navigate_step(&network, step, &cur);
+        let next = navigate_step(network, step, cur);
         count += 1;
         if next == "ZZZ" {
             return Some(count);
@@ -89,25 +88,24 @@ fn find_starts(network: &Network) -> Vec<&Node> {
         .collect()
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (ins, network) = parse(input);
-    let starts = find_starts(&network);
-    println!("{starts:?}");
+pub fn part_two((ins, network): &(Instructions, Network)) -> Option<u64> {
+    let starts = find_starts(network);
+    advent_of_code::trace!("{starts:?}");
     let mut counts = Vec::new();
     for start in starts.iter() {
         let mut cur = *start;
-        println!("start: {cur}");
+        advent_of_code::trace!("start: {cur}");
         let mut count = 0;
         let mut it = ins.iter().cycle();
         while let Some(step) = it.next() {
             count += 1;
-            let next = navigate_step(&network, step, cur);
+            let next = navigate_step(network, step, cur);
             if next.ends_with("Z") {
                 break;
             }
             cur = next;
         }
-        println!("found: {count}");
+        advent_of_code::trace!("found: {count}");
         counts.push(count);
     }
     // Find lcm over multiple counts
@@ -118,43 +116,8 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(multiple)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input1 = "RL
-
-AAA = (BBB, CCC)
-BBB = (DDD, EEE)
-CCC = (ZZZ, GGG)
-DDD = (DDD, DDD)
-EEE = (EEE, EEE)
-GGG = (GGG, GGG)
-ZZZ = (ZZZ, ZZZ)";
-        assert_eq!(part_one(input1), Some(2));
-        let input2 = "LLR
-
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)";
-        assert_eq!(part_one(input2), Some(6));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = "LR
-
-11A = (11B, XXX)
-11B = (XXX, 11Z)
-11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
-XXX = (XXX, XXX)";
-        let result = part_two(&input);
-        assert_eq!(result, Some(6));
-    }
+advent_of_code::solution_tests! {
+    parse = parse,
+    part_one: { simple: Some(2), repeating: Some(6) },
+    part_two: { ghosts: Some(6) },
 }
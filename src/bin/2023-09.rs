@@ -50,24 +50,17 @@ pub fn part_two(input: &str) -> Option<i32> {
     Some(sequences.iter().map(extrapolate_prev).sum())
 }
 
+advent_of_code::solution_tests! {
+    part_one: Some(114),
+    part_two: Some(2),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(114));
-    }
-
     #[test]
     fn test_derivatives() {
         assert_eq!(derivates(&vec![0, 3, 6, 9, 12, 15]), vec![3, 3, 3, 3, 3]);
     }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
-    }
 }
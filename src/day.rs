@@ -1,8 +1,13 @@
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
+use std::time::SystemTime;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+use crate::Year;
+
+/// A valid day number of an [`Event`](crate::Event) (i.e. an integer in range 1 to
+/// [`Day::MAX`]). Whether a year has the day is up to its event, see
+/// [`Year::check_day`](crate::Year::check_day).
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -16,10 +21,13 @@ use std::str::FromStr;
 pub struct Day(u8);
 
 impl Day {
+    /// The most days that a year of any event in [`EVENTS`](crate::EVENTS) has.
+    pub const MAX: u8 = crate::event::max_day_count();
+
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > Self::MAX {
             return None;
         }
         Some(Self(day))
@@ -32,7 +40,7 @@ impl Day {
     }
 
     /// Converts the [`Day`] into an [`u8`].
-    pub fn into_inner(self) -> u8 {
+    pub const fn into_inner(self) -> u8 {
         self.0
     }
 }
@@ -74,18 +82,18 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(f, "expecting a day number between 1 and {}", Day::MAX)
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day from the 1st to [`Day::MAX`].
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day from the 1st to [`Day::MAX`].
 pub struct AllDays {
     current: u8,
 }
@@ -101,10 +109,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > Day::MAX {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the max.
         let day = Day(self.current);
         self.current += 1;
 
@@ -114,16 +122,170 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// A selection of days, parsed from a comma-separated list of terms:
+///  - `7`: a single day.
+///  - `1-5`, `12-` or `-5`: a range of days, which may be open at either end.
+///  - `odd`, `even` or `all`: the odd, the even or all days.
+///  - `solved`: the days that have a solution.
+///  - `latest`: the last day that has unlocked.
+///
+/// The set holds the days that match any of its terms. Which days these are depends on the year,
+/// see [`DaySet::days`].
+///
+/// ```
+/// # use advent_of_code::{DaySet, Year};
+/// let set: DaySet = "1-3,7,odd".parse().unwrap();
+/// let days = set.days(Year::new(2023).unwrap(), |_| false);
+/// let days: Vec<u8> = days.into_iter().map(|day| day.into_inner()).collect();
+/// assert_eq!(days[..6], [1, 2, 3, 5, 7, 9]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySet {
+    terms: Vec<DayTerm>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DayTerm {
+    /// The days from the first to the second, or to the last day of the year.
+    Range(u8, Option<u8>),
+    Odd,
+    Even,
+    All,
+    Solved,
+    Latest,
+}
+
+impl DaySet {
+    /// The days of a year in this set, in ascending order. `is_solved` tells whether a day has a
+    /// solution, for `solved`. Days that the year doesn't have are left out.
+    pub fn days(&self, year: Year, is_solved: impl Fn(Day) -> bool) -> Vec<Day> {
+        self.days_at(year, is_solved, SystemTime::now())
+    }
+
+    fn days_at(&self, year: Year, is_solved: impl Fn(Day) -> bool, now: SystemTime) -> Vec<Day> {
+        let latest = year
+            .days()
+            .filter(|&day| year.event().unlocks_at(year.into_inner(), day) <= now)
+            .last();
+
+        year.days()
+            .filter(|&day| {
+                self.terms.iter().any(|term| match *term {
+                    DayTerm::Range(from, to) => day >= from && to.is_none_or(|to| day <= to),
+                    DayTerm::Odd => day.0 % 2 == 1,
+                    DayTerm::Even => day.0 % 2 == 0,
+                    DayTerm::All => true,
+                    DayTerm::Solved => is_solved(day),
+                    DayTerm::Latest => latest == Some(day),
+                })
+            })
+            .collect()
+    }
+}
+
+impl From<Day> for DaySet {
+    fn from(day: Day) -> Self {
+        Self {
+            terms: vec![DayTerm::Range(day.0, Some(day.0))],
+        }
+    }
+}
+
+impl Display for DaySet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let terms: Vec<String> = self
+            .terms
+            .iter()
+            .map(|term| match *term {
+                DayTerm::Range(from, Some(to)) if from == to => from.to_string(),
+                DayTerm::Range(from, Some(to)) => format!("{from}-{to}"),
+                DayTerm::Range(from, None) => format!("{from}-"),
+                DayTerm::Odd => "odd".into(),
+                DayTerm::Even => "even".into(),
+                DayTerm::All => "all".into(),
+                DayTerm::Solved => "solved".into(),
+                DayTerm::Latest => "latest".into(),
+            })
+            .collect();
+        write!(f, "{}", terms.join(","))
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || DaySetFromStrError(s.to_string());
+        let day = |s: &str| {
+            s.trim()
+                .parse::<Day>()
+                .map(Day::into_inner)
+                .map_err(|_| error())
+        };
+
+        let terms = s
+            .split(',')
+            .map(|term| {
+                let term = term.trim().to_lowercase();
+                Ok(match term.as_str() {
+                    "odd" => DayTerm::Odd,
+                    "even" => DayTerm::Even,
+                    "all" => DayTerm::All,
+                    "solved" => DayTerm::Solved,
+                    "latest" => DayTerm::Latest,
+                    _ => match term.split_once('-') {
+                        Some((from, "")) => DayTerm::Range(day(from)?, None),
+                        Some(("", to)) => DayTerm::Range(1, Some(day(to)?)),
+                        Some((from, to)) => {
+                            let (from, to) = (day(from)?, day(to)?);
+                            if from > to {
+                                return Err(error());
+                            }
+                            DayTerm::Range(from, Some(to))
+                        }
+                        None => {
+                            let day = day(&term)?;
+                            DayTerm::Range(day, Some(day))
+                        }
+                    },
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { terms })
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid days `{}`, expecting e.g. `1-5,7,12-`, `odd`, `even`, `all`, `solved` or \
+             `latest` with days between 1 and {}",
+            self.0,
+            Day::MAX
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $day != 0 && $day <= $crate::Day::MAX,
             concat!(
                 "invalid day number `",
                 $day,
-                "`, expecting a value between 1 and 25"
+                "`, expecting a value between 1 and `Day::MAX`"
             ),
         );
         $crate::Day::__new_unchecked($day)
@@ -134,7 +296,51 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{all_days, Day, DaySet};
+    use crate::year;
+
+    fn days(set: &str, now_secs: u64) -> Vec<u8> {
+        let set: DaySet = set.parse().unwrap();
+        let now = UNIX_EPOCH + Duration::from_secs(now_secs);
+        set.days_at(year!(2023), |day| day == 2 || day == 8, now)
+            .into_iter()
+            .map(Day::into_inner)
+            .collect()
+    }
+
+    #[test]
+    fn selects_days() {
+        // 2023-12-10 12:00 UTC, after day 10 unlocked.
+        let now = 1_702_209_600;
+        assert_eq!(days("1-3,7", now), [1, 2, 3, 7]);
+        assert_eq!(days("23-", now), [23, 24, 25]);
+        assert_eq!(days("-2, 5", now), [1, 2, 5]);
+        assert_eq!(days("odd", now).len(), 13);
+        assert_eq!(days("even,1", now).len(), 13);
+        assert_eq!(days("all", now).len(), 25);
+        assert_eq!(days("solved,latest", now), [2, 8, 10]);
+        assert_eq!(days("latest", 0), []);
+    }
+
+    #[test]
+    fn leaves_out_days_of_other_years() {
+        let set: DaySet = "10-".parse().unwrap();
+        let days = set.days(year!(2025), |_| true);
+        assert_eq!(days.len(), 3);
+    }
+
+    #[test]
+    fn parses_day_sets() {
+        let set: DaySet = "1-5, 7,12-,ODD".parse().unwrap();
+        assert_eq!(set.to_string(), "1-5,7,12-,odd");
+        assert_eq!(DaySet::from(Day(8)).to_string(), "8");
+
+        for invalid in ["", "0", "26", "5-3", "1-2-3", "first", "1,,2"] {
+            assert!(invalid.parse::<DaySet>().is_err(), "{invalid}");
+        }
+    }
 
     #[test]
     fn all_days_iterator() {
@@ -0,0 +1,240 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{Day, Year};
+
+/// A calendar-style puzzle event, e.g. Advent of Code or Everybody Codes.
+///
+/// An event defines which days each of its years has, how many parts a day has, how its files
+/// are named and when its days unlock. [`Year`], the `solution!` macro, the runner and the
+/// commands read these from the event of a year. To add an event, declare it as a constant and
+/// list it in [`EVENTS`].
+///
+/// ```
+/// # use advent_of_code::{Year, EVERYBODY_CODES};
+/// let year: Year = "ec2024".parse().unwrap();
+/// assert_eq!(year.event(), &EVERYBODY_CODES);
+/// assert_eq!(year.day_count(), 20);
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct Event {
+    /// The name of the event, e.g. `Advent of Code`.
+    pub name: &'static str,
+    /// Prefixes the years of the event in arguments and file names, e.g. `ec` for
+    /// `src/bin/ec2024-01.rs` and `data/ec2024`. At most one event has an empty prefix.
+    pub prefix: &'static str,
+    /// What a day is called, e.g. `Quest`.
+    pub day_label: &'static str,
+    /// The year of the first event.
+    pub first_year: u16,
+    /// The number of days per year, as `(year, days)` pairs that apply from that year on, in
+    /// ascending order of years.
+    pub day_counts: &'static [(u16, u8)],
+    /// The number of parts of a day.
+    pub parts: u8,
+    /// When the days unlock.
+    pub schedule: Schedule,
+    /// Whether puzzles and inputs can be downloaded and answers submitted with aoc-cli.
+    pub aoc_cli: bool,
+}
+
+/// When the days of an event unlock.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Schedule {
+    /// The month of the first day.
+    pub month: u8,
+    /// The day of the month of the first day. With `weekdays_only`, the first day unlocks on the
+    /// first Monday on or after this date.
+    pub day: u8,
+    /// The hour in UTC at which days unlock.
+    pub hour_utc: u8,
+    /// Whether days only unlock from Monday to Friday.
+    pub weekdays_only: bool,
+}
+
+pub const ADVENT_OF_CODE: Event = Event {
+    name: "Advent of Code",
+    prefix: "",
+    day_label: "Day",
+    first_year: 2015,
+    day_counts: &[(2015, 25), (2025, 12)],
+    parts: 2,
+    schedule: Schedule {
+        month: 12,
+        day: 1,
+        hour_utc: 5,
+        weekdays_only: false,
+    },
+    aoc_cli: true,
+};
+
+pub const EVERYBODY_CODES: Event = Event {
+    name: "Everybody Codes",
+    prefix: "ec",
+    day_label: "Quest",
+    first_year: 2024,
+    day_counts: &[(2024, 20)],
+    parts: 3,
+    schedule: Schedule {
+        month: 11,
+        day: 1,
+        hour_utc: 23,
+        weekdays_only: true,
+    },
+    aoc_cli: false,
+};
+
+/// The events that years can belong to.
+pub const EVENTS: &[Event] = &[ADVENT_OF_CODE, EVERYBODY_CODES];
+
+impl Event {
+    /// Finds the event with a prefix in [`EVENTS`].
+    #[must_use]
+    pub const fn find(prefix: &str) -> Option<&'static Event> {
+        let mut ix = 0;
+        while ix < EVENTS.len() {
+            if str_eq(EVENTS[ix].prefix, prefix) {
+                return Some(&EVENTS[ix]);
+            }
+            ix += 1;
+        }
+        None
+    }
+
+    /// Creates a [`Year`] of this event, if the event took place in it.
+    #[must_use]
+    pub fn year(&self, year: u16) -> Option<Year> {
+        (year >= self.first_year).then(|| Year::__new_unchecked(self.prefix, year))
+    }
+
+    /// The number of days of a year of this event.
+    #[must_use]
+    pub const fn day_count(&self, year: u16) -> u8 {
+        let mut count = 0;
+        let mut ix = 0;
+        while ix < self.day_counts.len() {
+            let (from, days) = self.day_counts[ix];
+            if year >= from {
+                count = days;
+            }
+            ix += 1;
+        }
+        count
+    }
+
+    /// The time at which a day of a year of this event unlocks.
+    #[must_use]
+    pub fn unlocks_at(&self, year: u16, day: Day) -> SystemTime {
+        let schedule = &self.schedule;
+        let mut date = days_from_civil(year, schedule.month, schedule.day);
+
+        if schedule.weekdays_only {
+            while weekday(date) != 1 {
+                date += 1;
+            }
+            for _ in 1..day.into_inner() {
+                date += 1;
+                while weekday(date) == 0 || weekday(date) == 6 {
+                    date += 1;
+                }
+            }
+        } else {
+            date += u64::from(day.into_inner() - 1);
+        }
+
+        UNIX_EPOCH + Duration::from_secs(date * 86_400 + u64::from(schedule.hour_utc) * 3_600)
+    }
+}
+
+/// The most days that a year of any event has.
+#[must_use]
+pub const fn max_day_count() -> u8 {
+    let mut max = 0;
+    let mut ix = 0;
+    while ix < EVENTS.len() {
+        let counts = EVENTS[ix].day_counts;
+        let mut jx = 0;
+        while jx < counts.len() {
+            if counts[jx].1 > max {
+                max = counts[jx].1;
+            }
+            jx += 1;
+        }
+        ix += 1;
+    }
+    max
+}
+
+pub(crate) const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut ix = 0;
+    while ix < a.len() {
+        if a[ix] != b[ix] {
+            return false;
+        }
+        ix += 1;
+    }
+    true
+}
+
+/// The number of days from 1970-01-01 to a date, see
+/// <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+fn days_from_civil(year: u16, month: u8, day: u8) -> u64 {
+    let (month, day) = (u64::from(month), u64::from(day));
+    let year = u64::from(year) - u64::from(month <= 2);
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The day of the week of a date from [`days_from_civil`], from 0 (Sunday) to 6 (Saturday).
+fn weekday(days: u64) -> u64 {
+    (days + 4) % 7
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{days_from_civil, max_day_count, Event, ADVENT_OF_CODE, EVERYBODY_CODES};
+    use crate::day;
+
+    #[test]
+    fn finds_events() {
+        assert_eq!(Event::find(""), Some(&ADVENT_OF_CODE));
+        assert_eq!(Event::find("ec"), Some(&EVERYBODY_CODES));
+        assert_eq!(Event::find("xy"), None);
+    }
+
+    #[test]
+    fn counts_days() {
+        assert_eq!(ADVENT_OF_CODE.day_count(2024), 25);
+        assert_eq!(ADVENT_OF_CODE.day_count(2025), 12);
+        assert_eq!(EVERYBODY_CODES.day_count(2025), 20);
+        assert_eq!(max_day_count(), 25);
+    }
+
+    #[test]
+    fn converts_dates() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(2023, 12, 1), 19_692);
+    }
+
+    #[test]
+    fn schedules_days() {
+        let at = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+        // 2023-12-01 05:00 and 2023-12-25 05:00 UTC
+        assert_eq!(ADVENT_OF_CODE.unlocks_at(2023, day!(1)), at(1_701_406_800));
+        assert_eq!(ADVENT_OF_CODE.unlocks_at(2023, day!(25)), at(1_703_480_400));
+        // 2024-11-04 23:00 UTC, a Monday, and 2024-11-11 23:00 UTC after a weekend
+        assert_eq!(EVERYBODY_CODES.unlocks_at(2024, day!(1)), at(1_730_761_200));
+        assert_eq!(EVERYBODY_CODES.unlocks_at(2024, day!(6)), at(1_731_366_000));
+    }
+}
@@ -1,7 +1,66 @@
 mod day;
+mod event;
 pub mod template;
+mod year;
+
+use std::{
+    fmt::Display,
+    num::{ParseFloatError, ParseIntError},
+};
 
 pub use day::*;
+pub use event::*;
+pub use year::*;
 
+/// An error for parsing puzzle input. Converts from the standard parse errors and from messages,
+/// so that it can be used with `?`:
+///
+/// ```ignore
+/// let (id, rest) = line.split_once(':').ok_or("missing `:`")?;
+/// let id: u32 = id.parse()?;
+/// ```
 #[derive(Debug, PartialEq, Eq)]
-pub struct ParseError;
+pub struct ParseError {
+    message: String,
+}
+
+impl ParseError {
+    #[must_use]
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "parse error: {}", self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<&str> for ParseError {
+    fn from(message: &str) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<String> for ParseError {
+    fn from(message: String) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<ParseIntError> for ParseError {
+    fn from(e: ParseIntError) -> Self {
+        Self::new(e.to_string())
+    }
+}
+
+impl From<ParseFloatError> for ParseError {
+    fn from(e: ParseFloatError) -> Self {
+        Self::new(e.to_string())
+    }
+}
@@ -1,56 +1,122 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{all, download, fuzz, read, scaffold, solve};
+use advent_of_code::template::registry;
+use advent_of_code::{Day, DaySet, Year};
 use args::{parse, AppArguments};
+use solutions::SOLUTIONS;
+
+/// The solutions of all days, see [`advent_of_code::template::registry`].
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
 
 mod args {
+    use std::convert::Infallible;
+    use std::ffi::OsString;
+    use std::path::PathBuf;
     use std::process;
 
-    use advent_of_code::Day;
+    use advent_of_code::template::bench::BenchConfig;
+    use advent_of_code::template::commands::{all::Format, fuzz::FuzzOptions, RunOptions};
+    use advent_of_code::template::input::Input;
+    use advent_of_code::template::watchdog::parse_duration;
+    use advent_of_code::template::{trace, visualize};
+    use advent_of_code::{Day, DaySet, Year};
 
     pub enum AppArguments {
         Download {
+            year: Year,
+            days: DaySet,
+        },
+        Fuzz {
+            year: Year,
             day: Day,
+            options: FuzzOptions,
         },
         Read {
+            year: Year,
             day: Day,
         },
         Scaffold {
-            day: Day,
+            year: Year,
+            days: DaySet,
         },
         Solve {
+            year: Year,
             day: Day,
-            release: bool,
-            time: bool,
+            options: RunOptions,
             submit: Option<u8>,
+            input: Option<Input>,
         },
         All {
-            release: bool,
-            time: bool,
+            /// Every year that has solutions if [`None`].
+            year: Option<Year>,
+            options: RunOptions,
+            format: Format,
+            /// All days if [`None`].
+            days: Option<DaySet>,
         },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        let (verbose, args) = take_verbosity(std::env::args_os().skip(1).collect());
+        let mut args = pico_args::Arguments::from_vec(args);
 
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
-            },
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
-            },
+            Some(command @ ("all" | "bench")) => {
+                let year = args.opt_value_from_str("--year")?;
+                let mut options = parse_run_options(&mut args, verbose)?;
+                // `bench` times the solutions and updates the readme with their benchmarks.
+                if command == "bench" {
+                    options.release = true;
+                    options.time = true;
+                }
+                AppArguments::All {
+                    year,
+                    options,
+                    format: args.opt_value_from_str("--format")?.unwrap_or(Format::Text),
+                    days: args.opt_free_from_str()?,
+                }
+            }
+            Some("download") => {
+                let (year, days) = parse_year_and_days(&mut args)?;
+                AppArguments::Download { year, days }
+            }
+            Some("fuzz") => {
+                let (year, day) = parse_year_and_day(&mut args)?;
+                let defaults = FuzzOptions::default();
+                AppArguments::Fuzz {
+                    year,
+                    day,
+                    options: FuzzOptions {
+                        runs: args.opt_value_from_str("--runs")?.unwrap_or(defaults.runs),
+                        seed: args.opt_value_from_str("--seed")?.unwrap_or(defaults.seed),
+                        timeout: args
+                            .opt_value_from_fn("--timeout", parse_duration)?
+                            .unwrap_or(defaults.timeout),
+                        input: args.opt_value_from_os_str("--child", |s| {
+                            Ok::<_, Infallible>(PathBuf::from(s))
+                        })?,
+                    },
+                }
+            }
+            Some("read") => {
+                let (year, day) = parse_year_and_day(&mut args)?;
+                AppArguments::Read { year, day }
+            }
+            Some("scaffold") => {
+                let (year, days) = parse_year_and_days(&mut args)?;
+                AppArguments::Scaffold { year, days }
+            }
+            Some("solve") => {
+                let (year, day) = parse_year_and_day(&mut args)?;
+                AppArguments::Solve {
+                    year,
+                    day,
+                    options: parse_run_options(&mut args, verbose)?,
+                    submit: args.opt_value_from_str("--submit")?,
+                    input: Input::from_args(&mut args)?,
+                }
+            }
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -68,6 +134,70 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parses `--year`, falling back to the `AOC_YEAR` environment variable.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn std::error::Error>> {
+        match args.opt_value_from_str("--year")? {
+            Some(year) => Ok(year),
+            None => Year::from_env()
+                .ok_or_else(|| "no year given, pass `--year` or set `AOC_YEAR`.".into()),
+        }
+    }
+
+    /// Parses the year and a day that is part of it.
+    fn parse_year_and_day(
+        args: &mut pico_args::Arguments,
+    ) -> Result<(Year, Day), Box<dyn std::error::Error>> {
+        let year = parse_year(args)?;
+        let day = year.check_day(args.free_from_str()?)?;
+        Ok((year, day))
+    }
+
+    /// Parses the year and a [`DaySet`], which is resolved against the solutions later on.
+    fn parse_year_and_days(
+        args: &mut pico_args::Arguments,
+    ) -> Result<(Year, DaySet), Box<dyn std::error::Error>> {
+        let year = parse_year(args)?;
+        Ok((year, args.free_from_str()?))
+    }
+
+    fn parse_run_options(
+        args: &mut pico_args::Arguments,
+        verbose: u8,
+    ) -> Result<RunOptions, Box<dyn std::error::Error>> {
+        Ok(RunOptions {
+            release: args.contains("--release"),
+            time: args.contains("--time"),
+            alloc: args.contains("--alloc"),
+            timeout: args.opt_value_from_fn("--timeout", parse_duration)?,
+            bench: BenchConfig::from_args(args)?,
+            verbose,
+            log: args.contains("--log"),
+            visualize: visualize::Config::from_args(args)?,
+            isolated: args.contains("--isolated"),
+            variant: args.opt_value_from_str("--variant")?,
+            variants: args.contains("--variants"),
+        })
+    }
+
+    /// Takes the `-v`, `-vv`, `-vvvv`, ... and `--verbose` flags out of the arguments and counts
+    /// them, see [`trace::flag_level`].
+    fn take_verbosity(args: Vec<OsString>) -> (u8, Vec<OsString>) {
+        let mut verbose: usize = 0;
+
+        let rest = args
+            .into_iter()
+            .filter(|arg| match arg.to_str().and_then(trace::flag_level) {
+                Some(level) => {
+                    verbose += level;
+                    false
+                }
+                None => true,
+            })
+            .collect();
+
+        (u8::try_from(verbose).unwrap_or(u8::MAX), rest)
+    }
 }
 
 fn main() {
@@ -77,16 +207,39 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::All {
+                year,
+                options,
+                format,
+                days,
+            } => all::handle(SOLUTIONS, year, days.as_ref(), &options, format),
+            AppArguments::Download { year, days } => {
+                download::handle(year, &select_days(year, &days));
+            }
+            AppArguments::Fuzz { year, day, options } => {
+                fuzz::handle(SOLUTIONS, year, day, &options);
+            }
+            AppArguments::Read { year, day } => read::handle(year, day),
+            AppArguments::Scaffold { year, days } => {
+                scaffold::handle(year, &select_days(year, &days));
+            }
             AppArguments::Solve {
+                year,
                 day,
-                release,
-                time,
+                options,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                input,
+            } => solve::handle(SOLUTIONS, year, day, &options, submit, input.as_ref()),
         },
     };
 }
+
+/// The days of a year in a [`DaySet`], exiting if there are none.
+fn select_days(year: Year, days: &DaySet) -> Vec<Day> {
+    let selected = registry::select_days(SOLUTIONS, year, days);
+    if selected.is_empty() {
+        eprintln!("No days of {year} match `{days}`.");
+        std::process::exit(1);
+    }
+    selected
+}
@@ -0,0 +1,211 @@
+/// Accepted answers of each day, used to detect when a change alters a known answer.
+///
+/// The answers of a day live in `data/<year>/answers/DD.txt`, one `part: answer` pair per line:
+///
+/// ```text
+/// 1: 142
+/// 2: 281
+/// ```
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::template::get_data_path;
+use crate::{Day, Year};
+
+/// The result of comparing an answer against the accepted one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    /// The answer matches the accepted answer.
+    Match,
+    /// The answer differs from the accepted answer.
+    Mismatch,
+    /// No accepted answer is known for this part.
+    Unknown,
+}
+
+impl Check {
+    /// The mark shown next to a result.
+    #[must_use]
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Check::Match => "✓",
+            Check::Mismatch => "✗",
+            Check::Unknown => "?",
+        }
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Check::Match => "match",
+            Check::Mismatch => "mismatch",
+            Check::Unknown => "unknown",
+        })
+    }
+}
+
+impl FromStr for Check {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "match" => Ok(Check::Match),
+            "mismatch" => Ok(Check::Mismatch),
+            "unknown" => Ok(Check::Unknown),
+            _ => Err(format!("unknown check `{s}`")),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(msg) => write!(f, "malformed answers file: {msg}"),
+            Error::IO(e) => write!(f, "could not access answers file: {e}"),
+        }
+    }
+}
+
+/// The accepted answers of a day, keyed by part.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers(BTreeMap<String, String>);
+
+impl Answers {
+    /// Reads the answers of a day. A missing file yields no answers.
+    pub fn read(year: Year, day: Day) -> Result<Self, Error> {
+        Self::read_from(&get_answers_path(year, day))
+    }
+
+    /// Reads answers from a file. A missing file yields no answers.
+    pub fn read_from(path: &Path) -> Result<Self, Error> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        fs::read_to_string(path)?.parse()
+    }
+
+    /// Writes the answers of a day, creating the answers folder if needed.
+    pub fn write(&self, year: Year, day: Day) -> Result<(), Error> {
+        let path = get_answers_path(year, day);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn get(&self, part: &str) -> Option<&str> {
+        self.0.get(part).map(String::as_str)
+    }
+
+    /// Records the accepted answer of a part.
+    pub fn set(&mut self, part: &str, answer: &str) {
+        self.0.insert(part.into(), answer.into());
+    }
+
+    /// Compares an answer against the accepted answer of a part.
+    #[must_use]
+    pub fn check(&self, part: &str, answer: &str) -> Check {
+        match self.get(part) {
+            None => Check::Unknown,
+            Some(expected) if expected == answer.trim() => Check::Match,
+            Some(_) => Check::Mismatch,
+        }
+    }
+}
+
+impl FromStr for Answers {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Self::default();
+
+        for (ix, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (part, answer) = line.split_once(':').ok_or_else(|| {
+                Error::Parser(format!("expected `part: answer` on line {}", ix + 1))
+            })?;
+
+            answers.set(part.trim(), answer.trim());
+        }
+
+        Ok(answers)
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (part, answer) in &self.0 {
+            writeln!(f, "{part}: {answer}")?;
+        }
+        Ok(())
+    }
+}
+
+#[must_use]
+pub fn get_answers_path(year: Year, day: Day) -> PathBuf {
+    get_data_path(year)
+        .join("answers")
+        .join(format!("{day}.txt"))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Check};
+
+    #[test]
+    fn parses_answers() {
+        let answers: Answers = "1: 142\n\n# comment\n2:  281 \n".parse().unwrap();
+        assert_eq!(answers.get("1"), Some("142"));
+        assert_eq!(answers.get("2"), Some("281"));
+        assert_eq!(answers.get("3"), None);
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert!("1: 142\n281".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn round_trips_answers() {
+        let mut answers = Answers::default();
+        answers.set("2", "281");
+        answers.set("1", "EFGHIJKL");
+        assert_eq!(answers.to_string(), "1: EFGHIJKL\n2: 281\n");
+        assert_eq!(answers.to_string().parse::<Answers>().unwrap(), answers);
+    }
+
+    #[test]
+    fn checks_answers() {
+        let answers: Answers = "1: 142".parse().unwrap();
+        assert_eq!(answers.check("1", "142"), Check::Match);
+        assert_eq!(answers.check("1", "143"), Check::Mismatch);
+        assert_eq!(answers.check("2", "281"), Check::Unknown);
+    }
+}
@@ -0,0 +1,290 @@
+/// Settings of the benchmark that runs with `--time`.
+///
+/// Each setting can be given on the command line of `solve` and `all`, or via an environment
+/// variable. Command-line values take precedence; unset values fall back to the defaults.
+use std::{env, fmt::Display, str::FromStr, time::Duration};
+
+use crate::template::watchdog::parse_duration;
+
+const DEFAULT_BUDGET: Duration = Duration::from_secs(1);
+const DEFAULT_MIN_SAMPLES: u128 = 10;
+const DEFAULT_MAX_SAMPLES: u128 = 10_000;
+const DEFAULT_WARMUP: u128 = 0;
+
+/// Benchmark settings. Fields are [`None`] if they were not configured.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Approximate total time spent on the warm samples of a part.
+    pub budget: Option<Duration>,
+    pub min_samples: Option<u128>,
+    pub max_samples: Option<u128>,
+    /// Untimed iterations before the samples are collected.
+    pub warmup: Option<u128>,
+}
+
+impl BenchConfig {
+    /// Reads the settings from command-line arguments.
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        let config = Self {
+            budget: args.opt_value_from_fn("--bench-time", parse_duration)?,
+            min_samples: args.opt_value_from_str("--min-samples")?,
+            max_samples: args.opt_value_from_str("--max-samples")?,
+            warmup: args.opt_value_from_str("--warmup")?,
+        };
+
+        config
+            .validate()
+            .map_err(|e| pico_args::Error::ArgumentParsingFailed { cause: e })?;
+
+        Ok(config)
+    }
+
+    /// Reads the settings from the `AOC_BENCH_TIME`, `AOC_BENCH_MIN_SAMPLES`,
+    /// `AOC_BENCH_MAX_SAMPLES` and `AOC_BENCH_WARMUP` environment variables.
+    pub fn from_env() -> Result<Self, String> {
+        fn var<T>(
+            name: &str,
+            parse: impl Fn(&str) -> Result<T, String>,
+        ) -> Result<Option<T>, String> {
+            env::var(name)
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(|v| parse(&v).map_err(|e| format!("{name}: {e}")))
+                .transpose()
+        }
+
+        let config = Self {
+            budget: var("AOC_BENCH_TIME", parse_duration)?,
+            min_samples: var("AOC_BENCH_MIN_SAMPLES", parse_count)?,
+            max_samples: var("AOC_BENCH_MAX_SAMPLES", parse_count)?,
+            warmup: var("AOC_BENCH_WARMUP", parse_count)?,
+        };
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// The settings of the current solution binary: its arguments, then the environment.
+    #[must_use]
+    pub fn current() -> Self {
+        let mut args = pico_args::Arguments::from_env();
+
+        let from_args = Self::from_args(&mut args).unwrap_or_else(|e| {
+            eprintln!("Ignoring benchmark arguments: {e}");
+            Self::default()
+        });
+
+        let from_env = Self::from_env().unwrap_or_else(|e| {
+            eprintln!("Ignoring benchmark environment: {e}");
+            Self::default()
+        });
+
+        from_args.or(from_env)
+    }
+
+    /// Fills the settings that are not configured in `self` from `other`.
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            budget: self.budget.or(other.budget),
+            min_samples: self.min_samples.or(other.min_samples),
+            max_samples: self.max_samples.or(other.max_samples),
+            warmup: self.warmup.or(other.warmup),
+        }
+    }
+
+    /// The configured settings as arguments for a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(budget) = self.budget {
+            args.push("--bench-time".into());
+            // in microseconds, so that a budget below a millisecond survives.
+            args.push(format!("{}us", budget.as_micros()));
+        }
+
+        if let Some(min_samples) = self.min_samples {
+            args.push("--min-samples".into());
+            args.push(min_samples.to_string());
+        }
+
+        if let Some(max_samples) = self.max_samples {
+            args.push("--max-samples".into());
+            args.push(max_samples.to_string());
+        }
+
+        if let Some(warmup) = self.warmup {
+            args.push("--warmup".into());
+            args.push(warmup.to_string());
+        }
+
+        args
+    }
+
+    #[must_use]
+    pub fn budget(&self) -> Duration {
+        self.budget.unwrap_or(DEFAULT_BUDGET)
+    }
+
+    #[must_use]
+    pub fn min_samples(&self) -> u128 {
+        self.min_samples.unwrap_or(DEFAULT_MIN_SAMPLES)
+    }
+
+    #[must_use]
+    pub fn max_samples(&self) -> u128 {
+        // an explicit minimum above the default maximum raises the maximum.
+        self.max_samples
+            .unwrap_or_else(|| DEFAULT_MAX_SAMPLES.max(self.min_samples()))
+    }
+
+    #[must_use]
+    pub fn warmup(&self) -> u128 {
+        self.warmup.unwrap_or(DEFAULT_WARMUP)
+    }
+
+    /// The number of warm samples to collect for a part whose first run took `base_time`.
+    #[must_use]
+    pub fn iterations(&self, base_time: Duration) -> u128 {
+        let max_samples = self.max_samples();
+        let min_samples = self.min_samples().min(max_samples);
+        (self.budget().as_nanos() / base_time.as_nanos().max(10)).clamp(min_samples, max_samples)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        match (self.min_samples, self.max_samples) {
+            (Some(min), Some(max)) if min > max => Err(format!(
+                "minimum samples ({min}) must not exceed maximum samples ({max})"
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Display for BenchConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} budget · {}–{} samples · {} warmup",
+            self.budget(),
+            self.min_samples().min(self.max_samples()),
+            self.max_samples(),
+            self.warmup()
+        )
+    }
+}
+
+fn parse_count(s: &str) -> Result<u128, String> {
+    u128::from_str(s.trim()).map_err(|_| format!("invalid number `{s}`"))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BenchConfig;
+    use std::time::Duration;
+
+    fn args(args: &[&str]) -> pico_args::Arguments {
+        pico_args::Arguments::from_vec(args.iter().map(Into::into).collect())
+    }
+
+    #[test]
+    fn defaults() {
+        let config = BenchConfig::default();
+        assert_eq!(config.iterations(Duration::from_nanos(1)), 10_000);
+        assert_eq!(config.iterations(Duration::from_millis(1)), 1000);
+        assert_eq!(config.iterations(Duration::from_secs(5)), 10);
+        assert_eq!(
+            config.to_string(),
+            "1s budget · 10–10000 samples · 0 warmup"
+        );
+    }
+
+    #[test]
+    fn parses_args() {
+        let config = BenchConfig::from_args(&mut args(&[
+            "--bench-time",
+            "5s",
+            "--min-samples",
+            "1",
+            "--max-samples",
+            "1",
+            "--warmup",
+            "3",
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            config,
+            BenchConfig {
+                budget: Some(Duration::from_secs(5)),
+                min_samples: Some(1),
+                max_samples: Some(1),
+                warmup: Some(3),
+            }
+        );
+        assert_eq!(config.iterations(Duration::from_secs(3)), 1);
+        assert_eq!(
+            config.to_args(),
+            vec![
+                "--bench-time",
+                "5000000us",
+                "--min-samples",
+                "1",
+                "--max-samples",
+                "1",
+                "--warmup",
+                "3"
+            ]
+        );
+    }
+
+    #[test]
+    fn forwards_short_budgets() {
+        let config = BenchConfig::from_args(&mut args(&["--bench-time", "500us"])).unwrap();
+        assert_eq!(config.budget, Some(Duration::from_micros(500)));
+
+        let forwarded = BenchConfig::from_args(&mut args(
+            &config
+                .to_args()
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>(),
+        ))
+        .unwrap();
+        assert_eq!(forwarded, config);
+    }
+
+    #[test]
+    fn rejects_inverted_bounds() {
+        assert!(
+            BenchConfig::from_args(&mut args(&["--min-samples", "10", "--max-samples", "5"]))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn raises_default_maximum() {
+        let config = BenchConfig {
+            min_samples: Some(50_000),
+            ..BenchConfig::default()
+        };
+        assert_eq!(config.iterations(Duration::from_nanos(1)), 50_000);
+    }
+
+    #[test]
+    fn prefers_explicit_values() {
+        let args = BenchConfig {
+            warmup: Some(5),
+            ..BenchConfig::default()
+        };
+        let env = BenchConfig {
+            warmup: Some(1),
+            max_samples: Some(100),
+            ..BenchConfig::default()
+        };
+        let config = args.or(env);
+        assert_eq!(config.warmup, Some(5));
+        assert_eq!(config.max_samples, Some(100));
+    }
+}
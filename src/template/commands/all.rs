@@ -1,52 +1,319 @@
-use std::io;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::{io, process};
 
 use crate::template::{
+    answers::Check,
+    bench::BenchConfig,
+    commands::RunOptions,
     readme_benchmarks::{self, Timings},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    registry::{self, Solution},
+    report::{self, Record, Status},
+    runner,
+    unwind::EXIT_PANICKED,
+    watchdog, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{Day, DaySet, Year};
+
+/// Output format of the `all` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// The human-readable output of the solutions.
+    Text,
+    /// One JSON record per line for every stage of every solution.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format `{s}`, expecting `text` or `json`")),
+        }
+    }
+}
+
+/// Counts the outcomes of all parts of a run.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Tally {
+    solved: usize,
+    unsolved: usize,
+    /// Parts that returned an error.
+    failed: usize,
+    timed_out: usize,
+    panicked: usize,
+    /// Days whose binary failed outside of a part, e.g. because the input is missing.
+    failed_days: Vec<(Year, Day)>,
+    matched: usize,
+    unchecked: usize,
+    /// Parts whose answer differs from the accepted answer, with the details of the record.
+    mismatches: Vec<String>,
+}
+
+impl Tally {
+    fn add(&mut self, year: Year, day: Day, records: &[Record], exit_code: Option<i32>) {
+        for record in records.iter().filter(|r| r.part != "parse") {
+            match record.status {
+                Status::Solved => self.solved += 1,
+                Status::Unsolved => self.unsolved += 1,
+                Status::Failed => self.failed += 1,
+                Status::TimedOut => self.timed_out += 1,
+                Status::Panicked => self.panicked += 1,
+                Status::NotApplicable => {}
+            }
+
+            match record.check {
+                Check::Match => self.matched += 1,
+                Check::Unknown if record.answer.is_some() => self.unchecked += 1,
+                Check::Unknown => {}
+                Check::Mismatch => self.mismatches.push(format!(
+                    "{year} day {day} part {}: got {}, {}",
+                    record.part,
+                    record.answer.as_deref().unwrap_or("-"),
+                    record
+                        .message
+                        .as_deref()
+                        .unwrap_or("expected a different answer")
+                )),
+            }
+        }
+
+        // a panicking part is already counted above and ends the binary with `EXIT_PANICKED`.
+        if exit_code != Some(0) && exit_code != Some(EXIT_PANICKED) {
+            self.failed_days.push((year, day));
+        }
+    }
+
+    fn summary(&self) -> String {
+        let mut summary = format!(
+            "{} solved · {} unsolved · {} failed · {} panicked · {} timed out",
+            self.solved, self.unsolved, self.failed, self.panicked, self.timed_out
+        );
+
+        if !self.failed_days.is_empty() {
+            let days: Vec<String> = self
+                .failed_days
+                .iter()
+                .map(|(year, day)| format!("{year} day {day}"))
+                .collect();
+            summary.push_str(&format!("\nFailed to run: {}", days.join(", ")));
+        }
+
+        summary.push_str(&format!(
+            "\nAnswers: {} {} · {} {} · {} {}",
+            self.matched,
+            Check::Match.symbol(),
+            self.mismatches.len(),
+            Check::Mismatch.symbol(),
+            self.unchecked,
+            Check::Unknown.symbol(),
+        ));
+
+        for mismatch in &self.mismatches {
+            summary.push_str(&format!("\n  {} {mismatch}", Check::Mismatch.symbol()));
+        }
+
+        summary
+    }
+}
+
+/// The records reported by a solution and its exit code.
+pub type SolutionRun = (Vec<Record>, Option<i32>);
+
+/// Runs all days of a year, or of every year that has solutions if no year is given. With a
+/// [`DaySet`], only the days of each year in it run.
+pub fn handle(
+    solutions: &[Solution],
+    year: Option<Year>,
+    days: Option<&DaySet>,
+    options: &RunOptions,
+    format: Format,
+) {
+    let mut tally = Tally::default();
+    let is_text = format == Format::Text;
+
+    let years = match year {
+        Some(year) => vec![year],
+        None => registry::years(solutions),
+    };
+
+    let years: Vec<(Year, Vec<Day>)> = years
+        .into_iter()
+        .map(|year| match days {
+            Some(days) => (year, registry::select_days(solutions, year, days)),
+            None => (year, year.days().collect()),
+        })
+        .filter(|(_, days)| !days.is_empty())
+        .collect();
+
+    if let (Some(days), true) = (days, years.is_empty()) {
+        match year {
+            Some(year) => eprintln!("No days of {year} match `{days}`."),
+            None => eprintln!("No days match `{days}`."),
+        }
+        process::exit(1);
+    }
 
-pub fn handle(is_release: bool, is_timed: bool) {
+    for (ix, (year, year_days)) in years.into_iter().enumerate() {
+        if is_text {
+            if ix > 0 {
+                println!();
+            }
+
+            let title = format!("{} {}", year.event().name, year.into_inner());
+            println!("{ANSI_BOLD}{title}{ANSI_RESET}");
+            println!("{}", "=".repeat(title.len()));
+        }
+
+        // the benchmarks of a subset of days are merged into the year's table.
+        let merge = days.is_some();
+        run_year(
+            solutions, year, &year_days, merge, options, format, &mut tally,
+        );
+    }
+
+    if is_text {
+        println!("\n{ANSI_BOLD}Summary:{ANSI_RESET} {}", tally.summary());
+    }
+
+    // a changed answer fails the run, so that it can guard refactors.
+    if !tally.mismatches.is_empty() {
+        process::exit(1);
+    }
+}
+
+/// Runs days of a year, and with `--time` updates the year's benchmark table in the readme.
+/// With `merge`, the timings replace those of the same days in the table and the others are kept.
+fn run_year(
+    solutions: &[Solution],
+    year: Year,
+    days: &[Day],
+    merge: bool,
+    options: &RunOptions,
+    format: Format,
+    tally: &mut Tally,
+) {
     let mut timings: Vec<Timings> = vec![];
+    let is_text = format == Format::Text;
 
-    all_days().for_each(|day| {
-        if day > 1 {
+    let event = year.event();
+
+    for &day in days {
+        if is_text {
+            let title = format!("{} {day}", event.day_label);
             println!();
+            println!("{ANSI_BOLD}{title}{ANSI_RESET}");
+            println!("{}", "-".repeat(title.len()));
         }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+        let output = if options.is_isolated() {
+            child_commands::run_solution(year, day, options, format)
+        } else if has_time_limit(solutions, year, day) {
+            // built like this binary, so that its timings compare to those of the other days.
+            let options = RunOptions {
+                release: !cfg!(debug_assertions),
+                ..options.clone()
+            };
+            child_commands::run_solution(year, day, &options, format)
+        } else {
+            Ok(run_in_process(solutions, year, day, format))
+        };
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        // a binary that can't be run fails its day, like one that exits with an error.
+        let output = output.unwrap_or_else(|e| {
+            eprintln!(
+                "Failed to run {} {day}: {e}",
+                event.day_label.to_lowercase()
+            );
+            Some((vec![], None))
+        });
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+        match output {
+            None => {
+                if is_text {
+                    println!("Not solved.");
+                }
+            }
+            Some((records, exit_code)) => {
+                if !is_text {
+                    records.iter().for_each(|r| println!("{}", r.to_json()));
+                }
+                tally.add(year, day, &records, exit_code);
+                timings.push(child_commands::collect_timings(&records, day, event.parts));
+            }
         }
-    });
+    }
 
-    if is_timed {
+    if options.time {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+        if is_text {
+            println!(
+                "\n{ANSI_BOLD}Total {year}:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+            // the solution binaries resolve the same settings from their arguments and environment.
+            let bench = options
+                .bench
+                .clone()
+                .or(BenchConfig::from_env().unwrap_or_default());
+            println!("{ANSI_ITALIC}Benchmark: {bench}{ANSI_RESET}");
+        }
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
+        if options.release {
+            let updated = if merge {
+                readme_benchmarks::merge(year, timings)
+            } else {
+                readme_benchmarks::update(year, timings, total_millis)
+            };
+            match updated {
+                Ok(()) => eprintln!("Successfully updated README with {year} benchmarks."),
                 Err(_) => {
-                    eprintln!("Failed to update readme with benchmarks.");
+                    eprintln!("Failed to update readme with {year} benchmarks.");
                 }
             }
         }
     }
 }
 
+/// Whether the stages of a day are limited, by `--timeout` or the `timeout` option of its
+/// solution. Such a day runs in its own process: a stage that times out keeps running until its
+/// process exits, see [`watchdog`], and would slow down the days after it.
+fn has_time_limit(solutions: &[Solution], year: Year, day: Day) -> bool {
+    let Some(solution) = registry::find(solutions, year, day) else {
+        return false;
+    };
+
+    watchdog::reset_time_limit();
+    (solution.setup)();
+    let limit = watchdog::time_limit();
+    watchdog::reset_time_limit();
+
+    limit.is_some()
+}
+
+/// Runs the solution of a day in this process, see [`registry`].
+/// Returns [`None`] if the day has not been scaffolded yet.
+fn run_in_process(
+    solutions: &[Solution],
+    year: Year,
+    day: Day,
+    format: Format,
+) -> Option<SolutionRun> {
+    let solution = registry::find(solutions, year, day)?;
+
+    runner::set_quiet(format != Format::Text);
+    let (exit_code, records) = report::collect(|| runner::run_solution(&solution));
+
+    Some((records, Some(exit_code)))
+}
+
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
-    Parser(String),
+    Report(report::Error),
     IO(io::Error),
 }
 
@@ -56,48 +323,82 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl From<report::Error> for Error {
+    fn from(e: report::Error) -> Self {
+        Error::Report(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not read the output of the binary"),
+            Error::Report(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./src/bin/{}.rs", registry::get_bin_name(year, day))
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// Each solution also lives in its own binary, which `--isolated` runs instead.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting the records they report.
 mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
+    use super::{get_path_for_bin, Error, Format, RunOptions, SolutionRun};
+    use crate::template::registry;
+    use crate::template::report::{self, Record, Status, REPORT_FILE_ENV};
+    use crate::{Day, Year};
     use std::{
+        env, fs,
         io::{BufRead, BufReader},
         path::Path,
-        process::{Command, Stdio},
+        process::{self, Command, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day.
+    /// Returns the reported records and the exit code of the binary,
+    /// or [`None`] if the day has not been scaffolded yet.
+    pub fn run_solution(
+        year: Year,
+        day: Day,
+        options: &RunOptions,
+        format: Format,
+    ) -> Result<Option<SolutionRun>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&get_path_for_bin(year, day)).exists() {
+            return Ok(None);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut args: Vec<String> = vec![
+            "run".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            registry::get_bin_name(year, day),
+        ];
 
-        if is_release {
-            args.push("--release");
-        }
+        args.append(&mut options.cargo_args());
+
+        // mirror flags like `--time` to child invocations.
+        args.push("--".into());
+        args.append(&mut options.solution_args());
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        // the child appends its records to this file while it runs.
+        let report_path =
+            env::temp_dir().join(format!("aoc-report-{}-{year}-{day}.jsonl", process::id()));
+        if report_path.exists() {
+            fs::remove_file(&report_path)?;
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward stderr, and stdout unless only the records are printed.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
+            .env(REPORT_FILE_ENV, &report_path)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -105,85 +406,80 @@ mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
+        // output that isn't valid UTF-8 is forwarded lossily instead of ending the run.
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+            for line in stderr.split(b'\n').map_while(Result::ok) {
+                eprintln!("{}", String::from_utf8_lossy(&line));
+            }
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let mut forwarded = Ok(());
+        for line in stdout.split(b'\n') {
+            match line {
+                Ok(line) if format == Format::Text => {
+                    println!("{}", String::from_utf8_lossy(&line));
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    let _ = cmd.kill();
+                    forwarded = Err(e);
+                    break;
+                }
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let _ = thread.join();
+        let status = cmd.wait()?;
+        forwarded?;
+
+        let records = report::read(&report_path);
+        if report_path.exists() {
+            fs::remove_file(&report_path)?;
+        }
 
-        Ok(output)
+        Ok(Some((records?, status.code())))
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
+    /// Collects the benchmark timings of a day with `parts` parts from the records reported by
+    /// its binary.
+    pub fn collect_timings(records: &[Record], day: Day, parts: u8) -> super::Timings {
         let mut timings = super::Timings {
             day,
-            part_1: None,
-            part_2: None,
+            parse: None,
+            parts: vec![None; parts.into()],
             total_nanos: 0_f64,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+        for record in records.iter().filter(|r| r.day == day) {
+            let timing_str = match record.status {
+                Status::TimedOut => "timed out".into(),
+                Status::NotApplicable => "n/a".into(),
+                _ => format_nanos(record.nanos),
+            };
+
+            match (record.part.as_str(), record.part.parse::<usize>()) {
+                ("parse", _) => timings.parse = Some(timing_str),
+                (_, Ok(part)) if (1..=timings.parts.len()).contains(&part) => {
+                    timings.parts[part - 1] = Some(timing_str);
                 }
+                _ => {}
+            }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+            if record.status != Status::TimedOut {
+                #[allow(clippy::cast_precision_loss)]
+                {
+                    timings.total_nanos += record.nanos as f64;
                 }
-
-                timings.total_nanos += nanos;
-            });
+            }
+        }
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    fn format_nanos(nanos: u128) -> String {
+        #[allow(clippy::cast_possible_truncation)]
+        let duration = std::time::Duration::from_nanos(nanos as u64);
+        format!("{duration:.1?}")
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -202,53 +498,224 @@ mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
+        use super::collect_timings;
+
+        use crate::template::answers::Check;
+        use crate::template::report::{Record, Status};
+        use crate::{day, year};
+
+        fn record(part: &str, nanos: u128) -> Record {
+            Record {
+                year: year!(2023),
+                day: day!(1),
+                part: part.into(),
+                status: Status::Solved,
+                answer: Some("42".into()),
+                check: Check::Unknown,
+                message: None,
+                nanos,
+                samples: 100,
+            }
+        }
 
         #[test]
         fn test_well_formed() {
-            let res = parse_exec_time(
+            let res = collect_timings(&[record("1", 74_130), record("2", 74_130_000)], day!(1), 2);
+            assert_approx_eq!(res.total_nanos, 74204130_f64);
+            assert_eq!(res.parts[0].as_deref().unwrap(), "74.1µs");
+            assert_eq!(res.parts[1].as_deref().unwrap(), "74.1ms");
+        }
+
+        #[test]
+        fn test_parse_stage() {
+            let res = collect_timings(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    record("parse", 1_500),
+                    record("1", 74),
+                    record("2", 2_000_000_000),
                 ],
                 day!(1),
+                2,
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 2000001574_f64);
+            assert_eq!(res.parse.unwrap(), "1.5µs");
+            assert_eq!(res.parts[0].as_deref().unwrap(), "74.0ns");
+            assert_eq!(res.parts[1].as_deref().unwrap(), "2.0s");
         }
 
         #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
+        fn test_timed_out() {
+            let res = collect_timings(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    record("1", 74),
+                    Record {
+                        status: Status::TimedOut,
+                        answer: None,
+                        ..record("2", 10_000_000_000)
+                    },
                 ],
                 day!(1),
+                2,
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.total_nanos, 74_f64);
+            assert_eq!(res.parts[0].as_deref().unwrap(), "74.0ns");
+            assert_eq!(res.parts[1].as_deref().unwrap(), "timed out");
         }
 
         #[test]
-        fn test_missing_parts() {
-            let res = parse_exec_time(
+        fn test_single_part() {
+            let res = collect_timings(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    record("1", 74),
+                    Record {
+                        status: Status::NotApplicable,
+                        answer: None,
+                        ..record("2", 0)
+                    },
                 ],
                 day!(1),
+                2,
             );
+            assert_approx_eq!(res.total_nanos, 74_f64);
+            assert_eq!(res.parts[1].as_deref().unwrap(), "n/a");
+        }
+
+        #[test]
+        fn test_three_parts() {
+            let res = collect_timings(
+                &[record("1", 74), record("3", 2_000), record("extra", 5)],
+                day!(1),
+                3,
+            );
+            assert_eq!(res.parts[0].as_deref().unwrap(), "74.0ns");
+            assert_eq!(res.parts[1], None);
+            assert_eq!(res.parts[2].as_deref().unwrap(), "2.0µs");
+        }
+
+        #[test]
+        fn test_missing_parts() {
+            let res = collect_timings(&[], day!(1), 2);
             assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.parse.is_none(), true);
+            assert_eq!(res.parts, vec![None, None]);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Tally;
+    use crate::template::answers::Check;
+    use crate::template::report::{Record, Status};
+    use crate::template::unwind::EXIT_PANICKED;
+    use crate::{day, year};
+
+    fn record(part: &str, status: Status) -> Record {
+        Record {
+            year: year!(2023),
+            day: day!(1),
+            part: part.into(),
+            status,
+            answer: None,
+            check: Check::Unknown,
+            message: None,
+            nanos: 0,
+            samples: 1,
         }
     }
+
+    #[test]
+    fn tallies_parts() {
+        let mut tally = Tally::default();
+        tally.add(
+            year!(2023),
+            day!(1),
+            &[
+                record("parse", Status::Solved),
+                record("1", Status::Panicked),
+                record("2", Status::Solved),
+            ],
+            Some(EXIT_PANICKED),
+        );
+        tally.add(
+            year!(2023),
+            day!(2),
+            &[record("1", Status::Unsolved), record("2", Status::TimedOut)],
+            Some(0),
+        );
+        tally.add(
+            year!(2023),
+            day!(3),
+            &[record("1", Status::Failed), record("2", Status::Failed)],
+            Some(0),
+        );
+        tally.add(
+            year!(2023),
+            day!(25),
+            &[
+                record("1", Status::Solved),
+                record("2", Status::NotApplicable),
+            ],
+            Some(0),
+        );
+        assert_eq!(
+            tally,
+            Tally {
+                solved: 2,
+                unsolved: 1,
+                failed: 2,
+                timed_out: 1,
+                panicked: 1,
+                failed_days: vec![],
+                matched: 0,
+                unchecked: 0,
+                mismatches: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn tallies_checks() {
+        let mut tally = Tally::default();
+        tally.add(
+            year!(2023),
+            day!(5),
+            &[
+                Record {
+                    answer: Some("35".into()),
+                    check: Check::Match,
+                    ..record("1", Status::Solved)
+                },
+                Record {
+                    answer: Some("47".into()),
+                    check: Check::Mismatch,
+                    message: Some("expected 46".into()),
+                    ..record("2", Status::Solved)
+                },
+            ],
+            Some(0),
+        );
+        assert_eq!(tally.matched, 1);
+        assert_eq!(
+            tally.mismatches,
+            vec!["2023 day 05 part 2: got 47, expected 46"]
+        );
+        assert!(tally
+            .summary()
+            .ends_with("Answers: 1 ✓ · 1 ✗ · 0 ?\n  ✗ 2023 day 05 part 2: got 47, expected 46"));
+    }
+
+    #[test]
+    fn tallies_failed_binaries() {
+        let mut tally = Tally::default();
+        tally.add(year!(2023), day!(3), &[], Some(101));
+        tally.add(year!(2024), day!(4), &[], None);
+        assert_eq!(
+            tally.failed_days,
+            vec![(year!(2023), day!(3)), (year!(2024), day!(4))]
+        );
+        assert!(tally
+            .summary()
+            .contains("\nFailed to run: 2023 day 03, 2024 day 04\n"));
+    }
 }
@@ -1,15 +1,51 @@
 use crate::template::aoc_cli;
-use crate::Day;
+use crate::{Day, Year};
 use std::process;
+use std::time::SystemTime;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
+/// Downloads the puzzle and input of each of the days. Days that are still locked are skipped,
+/// and the command exits with an error if any day was skipped or failed.
+pub fn handle(year: Year, days: &[Day]) {
+    let event = year.event();
+
+    if !event.aoc_cli {
+        eprintln!("Inputs of {} can't be downloaded via aoc-cli.", event.name);
+        process::exit(1);
+    }
+
+    let mut failed = false;
+    let mut unlocked = vec![];
+
+    for &day in days {
+        let unlocks_at = event.unlocks_at(year.into_inner(), day);
+        match unlocks_at.duration_since(SystemTime::now()) {
+            Ok(remaining) => {
+                let minutes = remaining.as_secs().div_ceil(60);
+                eprintln!(
+                    "{} {day} of {year} unlocks in {}h {}m.",
+                    event.day_label,
+                    minutes / 60,
+                    minutes % 60
+                );
+                failed = true;
+            }
+            Err(_) => unlocked.push(day),
+        }
+    }
+
+    if !unlocked.is_empty() && aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    for day in unlocked {
+        if let Err(e) = aoc_cli::download(year, day) {
+            eprintln!("failed to call aoc-cli: {e}");
+            failed = true;
+        }
+    }
+
+    if failed {
         process::exit(1);
-    };
+    }
 }
@@ -0,0 +1,339 @@
+use std::io::{stdout, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::time::Duration;
+use std::{env, fs};
+
+use crate::template::answers::Check;
+use crate::template::embedded;
+use crate::template::examples::read_examples;
+use crate::template::fuzz::{self, Mutant, Rng};
+use crate::template::input::read_data_file;
+use crate::template::registry::{self, Solution};
+use crate::template::report::{self, Record, Status, REPORT_FILE_ENV};
+use crate::template::{get_data_path, runner, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::{Day, Year};
+
+/// Options of the `fuzz` command.
+#[derive(Debug, Clone)]
+pub struct FuzzOptions {
+    /// The number of random mutations, on top of the systematic ones.
+    pub runs: usize,
+    pub seed: u64,
+    /// The time limit of each stage.
+    pub timeout: Duration,
+    /// Runs the solution once on this file instead of fuzzing, in a child process of the command.
+    pub input: Option<PathBuf>,
+}
+
+impl Default for FuzzOptions {
+    fn default() -> Self {
+        Self {
+            runs: 200,
+            seed: 0,
+            timeout: Duration::from_secs(10),
+            input: None,
+        }
+    }
+}
+
+/// The number of candidates checked while minimising a crashing input.
+const MINIMISE_BUDGET: usize = 1000;
+
+/// Fuzzing stops after this many timeouts, since each one takes the full time limit.
+const MAX_TIMEOUTS: usize = 3;
+
+/// A mutated input that made a stage panic or time out.
+struct Finding {
+    record: Record,
+    source: String,
+    mutant: Mutant,
+    count: usize,
+}
+
+pub fn handle(solutions: &[Solution], year: Year, day: Day, options: &FuzzOptions) {
+    let event = year.event();
+
+    let Some(solution) = registry::find(solutions, year, day) else {
+        eprintln!(
+            "No solution for {} {day} of {year}, run `cargo scaffold {day} --year {year}` first.",
+            event.day_label.to_lowercase()
+        );
+        process::exit(1);
+    };
+
+    if let Some(path) = &options.input {
+        run_child(&solution, path, options.timeout);
+        return;
+    }
+
+    embedded::select(year, day, solution.files);
+    let sources = read_sources(year, day);
+    if sources.is_empty() {
+        eprintln!(
+            "{} {day} of {year} has neither an input nor examples to mutate.",
+            event.day_label
+        );
+        process::exit(1);
+    }
+
+    let mut mutants: Vec<(String, Mutant)> = vec![];
+    for (source, input) in &sources {
+        let unmodified = Mutant {
+            description: "unmodified".into(),
+            input: input.clone(),
+        };
+        mutants.push((source.clone(), unmodified));
+        for mutant in fuzz::systematic(input) {
+            mutants.push((source.clone(), mutant));
+        }
+    }
+
+    let mut rng = Rng::new(options.seed);
+    for ix in 0..options.runs {
+        let (source, input) = &sources[ix % sources.len()];
+        mutants.push((source.clone(), fuzz::random(input, &mut rng)));
+    }
+
+    let names: Vec<&str> = sources.iter().map(|(name, _)| name.as_str()).collect();
+    println!(
+        "{ANSI_BOLD}Fuzzing {} {day} of {year}{ANSI_RESET} on {} · {} runs · seed {}",
+        event.day_label.to_lowercase(),
+        names.join(", "),
+        mutants.len(),
+        options.seed
+    );
+
+    let mut findings: Vec<Finding> = vec![];
+    let mut runs = 0;
+    let mut timeouts = 0;
+
+    for (source, mutant) in mutants {
+        runs += 1;
+        print!("\r{ANSI_ITALIC}run {runs}{ANSI_RESET}");
+        let _ = stdout().flush();
+
+        let records = match run_mutant(&solution, &mutant.input, options.timeout) {
+            Ok(records) => records,
+            Err(e) => {
+                print!("\r");
+                eprintln!("Failed to run the solution: {e}");
+                process::exit(1);
+            }
+        };
+        let Some(record) = records.into_iter().find(is_finding) else {
+            continue;
+        };
+
+        if record.status == Status::TimedOut {
+            timeouts += 1;
+        }
+
+        match findings.iter_mut().find(|f| same_cause(&f.record, &record)) {
+            Some(finding) => finding.count += 1,
+            None => findings.push(Finding {
+                record,
+                source,
+                mutant,
+                count: 1,
+            }),
+        }
+
+        if timeouts >= MAX_TIMEOUTS {
+            print!("\r");
+            println!("Stopped after {timeouts} timeouts.             ");
+            break;
+        }
+    }
+
+    print!("\r");
+    println!(
+        "{} crashes · {} timeouts in {runs} runs             ",
+        findings
+            .iter()
+            .filter(|f| f.record.status == Status::Panicked)
+            .count(),
+        findings
+            .iter()
+            .filter(|f| f.record.status == Status::TimedOut)
+            .count(),
+    );
+
+    for (ix, finding) in findings.iter().enumerate() {
+        report_finding(&solution, ix + 1, finding, options.timeout);
+    }
+
+    if !findings.is_empty() {
+        process::exit(1);
+    }
+}
+
+/// Runs the solution on an input in a child process of this binary and returns the records of its
+/// stages. Every run gets a fresh process, so that the input and parsed values of the run are
+/// freed and a stage that timed out stops with it. A process that crashes without reporting a
+/// panic, e.g. on a stack overflow, is reported as a panic of the stage `process`.
+fn run_mutant(solution: &Solution, input: &str, timeout: Duration) -> Result<Vec<Record>, String> {
+    let temp_path = |kind: &str| env::temp_dir().join(format!("aoc-fuzz-{}.{kind}", process::id()));
+    let (input_path, report_path) = (temp_path("txt"), temp_path("jsonl"));
+
+    fs::write(&input_path, input).map_err(|e| e.to_string())?;
+    if report_path.exists() {
+        fs::remove_file(&report_path).map_err(|e| e.to_string())?;
+    }
+
+    let status = env::current_exe()
+        .and_then(|exe| {
+            Command::new(exe)
+                .args(["fuzz", &solution.day.to_string(), "--year"])
+                .arg(solution.year.to_string())
+                .arg("--timeout")
+                .arg(format!("{}ms", timeout.as_millis()))
+                .arg("--child")
+                .arg(&input_path)
+                .env(REPORT_FILE_ENV, &report_path)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut records = report::read(&report_path).map_err(|e| e.to_string())?;
+    let _ = fs::remove_file(&report_path);
+
+    if !status.success() && !records.iter().any(is_finding) {
+        records.push(Record {
+            year: solution.year,
+            day: solution.day,
+            part: "process".into(),
+            status: Status::Panicked,
+            answer: None,
+            check: Check::Unknown,
+            message: Some(format!("crashed with {status}")),
+            nanos: 0,
+            samples: 0,
+        });
+    }
+
+    Ok(records)
+}
+
+/// Runs the solution once on the input in `path`, as the child process of [`run_mutant`]. The
+/// records of its stages go to the report file of the parent.
+fn run_child(solution: &Solution, path: &Path, timeout: Duration) {
+    match fs::read_to_string(path) {
+        Ok(input) => {
+            runner::run_on_input(solution, &input, timeout);
+        }
+        Err(e) => {
+            eprintln!("Could not read \"{}\": {e}", path.display());
+            process::exit(1);
+        }
+    }
+}
+
+/// The inputs to mutate: the day's input, its example and its named examples, if they exist.
+fn read_sources(year: Year, day: Day) -> Vec<(String, String)> {
+    let mut sources = vec![];
+
+    for (name, folder) in [("input", "inputs"), ("example", "examples")] {
+        if let Ok(input) = read_data_file(folder, year, day) {
+            if !input.trim().is_empty() {
+                sources.push((name.to_string(), input));
+            }
+        }
+    }
+
+    for example in read_examples(year, day).unwrap_or_default() {
+        sources.push((format!("example `{}`", example.name), example.input));
+    }
+
+    sources
+}
+
+fn is_finding(record: &Record) -> bool {
+    matches!(record.status, Status::Panicked | Status::TimedOut)
+}
+
+/// Whether two findings have the same cause: a panic at the same location, or a timeout of the
+/// same stage.
+fn same_cause(a: &Record, b: &Record) -> bool {
+    a.status == b.status
+        && match a.status {
+            Status::Panicked => panic_location(a) == panic_location(b),
+            _ => a.part == b.part,
+        }
+}
+
+/// The location of a panic, from its message, e.g. `src/bin/2023-08.rs:40:10`.
+fn panic_location(record: &Record) -> &str {
+    let message = record.message.as_deref().unwrap_or_default();
+    message
+        .strip_prefix("panicked at ")
+        .and_then(|rest| rest.split_once(": "))
+        .map_or(message, |(location, _)| location)
+}
+
+/// Prints a finding and writes its input to `data/<year>/fuzz/<day>-<number>.txt`. Inputs that
+/// make a stage panic are minimised first; those that time out are not, since every candidate
+/// would take the full time limit.
+fn report_finding(solution: &Solution, number: usize, finding: &Finding, timeout: Duration) {
+    let record = &finding.record;
+    let stage = match record.part.parse::<u8>() {
+        Ok(part) => format!("part {part}"),
+        Err(_) => record.part.clone(),
+    };
+
+    println!();
+    println!(
+        "{ANSI_BOLD}{number}. {stage}{ANSI_RESET} {}",
+        record.message.as_deref().unwrap_or_default()
+    );
+    println!(
+        "   found with: {} · {} · {} run{}",
+        finding.mutant.description,
+        finding.source,
+        finding.count,
+        if finding.count == 1 { "" } else { "s" }
+    );
+
+    let input = if record.status == Status::Panicked {
+        fuzz::minimise(&finding.mutant.input, MINIMISE_BUDGET, |candidate| {
+            run_mutant(solution, candidate, timeout)
+                .is_ok_and(|records| records.iter().any(|other| same_cause(record, other)))
+        })
+    } else {
+        finding.mutant.input.clone()
+    };
+
+    let path = get_fuzz_path(solution.year, solution.day, number);
+    let written =
+        fs::create_dir_all(path.parent().unwrap()).and_then(|()| fs::write(&path, &input));
+
+    match written {
+        Ok(()) => println!("   input ({} bytes): \"{}\"", input.len(), path.display()),
+        Err(e) => eprintln!("   failed to write \"{}\": {e}", path.display()),
+    }
+
+    println!("   {}", preview(&input));
+}
+
+/// The input with line breaks and other special characters escaped, shortened if it is long.
+fn preview(input: &str) -> String {
+    const MAX_CHARS: usize = 200;
+
+    let escaped = format!("{input:?}");
+    if escaped.chars().count() <= MAX_CHARS {
+        escaped
+    } else {
+        let shortened: String = escaped.chars().take(MAX_CHARS).collect();
+        format!("{shortened}…")
+    }
+}
+
+/// The file that a finding's input is written to.
+fn get_fuzz_path(year: Year, day: Day, number: usize) -> PathBuf {
+    get_data_path(year)
+        .join("fuzz")
+        .join(format!("{day}-{number}.txt"))
+}
@@ -1,5 +1,99 @@
 pub mod all;
 pub mod download;
+pub mod fuzz;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+
+use std::time::Duration;
+
+use crate::template::bench::BenchConfig;
+use crate::template::visualize;
+
+/// Options that `solve` and `all` forward to the solution binaries.
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    pub release: bool,
+    pub time: bool,
+    pub alloc: bool,
+    pub timeout: Option<Duration>,
+    pub bench: BenchConfig,
+    /// The trace level, see [`trace`](crate::template::trace).
+    pub verbose: u8,
+    /// Whether trace output goes to a log file per day.
+    pub log: bool,
+    /// How frames pushed by the solutions are output.
+    pub visualize: visualize::Config,
+    /// Whether each solution runs in its own binary instead of in-process.
+    pub isolated: bool,
+    /// The variant that runs in place of the parts that have it.
+    pub variant: Option<String>,
+    /// Whether all variants run next to their parts and are compared with them.
+    pub variants: bool,
+}
+
+impl RunOptions {
+    /// Whether the solutions run in their own binaries: with `--isolated`, or if the options ask
+    /// for a build that differs from the running binary.
+    #[must_use]
+    pub fn is_isolated(&self) -> bool {
+        self.isolated
+            || (self.release && cfg!(debug_assertions))
+            || (self.alloc && !cfg!(feature = "alloc_stats"))
+    }
+
+    /// Arguments for `cargo run` that select how the solution is built.
+    #[must_use]
+    pub fn cargo_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if self.release {
+            args.push("--release".into());
+        }
+
+        if self.alloc {
+            args.push("--features".into());
+            args.push("alloc_stats".into());
+        }
+
+        args
+    }
+
+    /// Arguments for the solution binary itself.
+    #[must_use]
+    pub fn solution_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if self.time {
+            args.push("--time".into());
+        }
+
+        if let Some(timeout) = self.timeout {
+            args.push("--timeout".into());
+            args.push(format!("{}ms", timeout.as_millis()));
+        }
+
+        args.append(&mut self.bench.to_args());
+
+        if self.verbose > 0 {
+            args.push(format!("-{}", "v".repeat(self.verbose.into())));
+        }
+
+        if self.log {
+            args.push("--log".into());
+        }
+
+        args.append(&mut self.visualize.to_args());
+
+        if let Some(variant) = &self.variant {
+            args.push("--variant".into());
+            args.push(variant.clone());
+        }
+
+        if self.variants {
+            args.push("--variants".into());
+        }
+
+        args
+    }
+}
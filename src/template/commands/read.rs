@@ -1,15 +1,23 @@
 use std::process;
 
 use crate::template::aoc_cli;
-use crate::Day;
+use crate::{Day, Year};
+
+pub fn handle(year: Year, day: Day) {
+    if !year.event().aoc_cli {
+        eprintln!(
+            "Puzzles of {} can't be read via aoc-cli.",
+            year.event().name
+        );
+        process::exit(1);
+    }
 
-pub fn handle(day: Day) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
+    if let Err(e) = aoc_cli::read(year, day) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
@@ -1,94 +1,135 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::Day;
+use crate::template::registry;
+use crate::{Day, Year};
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBERPARTS_OPTION);
 
-pub fn part_one(input: &str) -> Option<u32> {
-    None
-}
+PART_FUNCTIONS
+advent_of_code::solution_tests! {
+PART_TESTS}
+"#;
 
-pub fn part_two(input: &str) -> Option<u32> {
+const PART_TEMPLATE: &str = r#"pub fn PART_NAME(input: &str) -> Option<u32> {
     None
 }
+"#;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// A part without an expected answer, which `solution_tests!` ignores until one is added.
+const TEST_TEMPLATE: &str = "    PART_NAME,\n";
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+/// The name of a part's function, e.g. `part_one`.
+fn part_name(part: u8) -> String {
+    const NAMES: [&str; 5] = ["one", "two", "three", "four", "five"];
+    match NAMES.get(usize::from(part) - 1) {
+        Some(name) => format!("part_{name}"),
+        None => format!("part_{part}"),
     }
+}
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
+/// The module of a new solution with a function and a test per part of the event's days.
+fn module_contents(year: Year, day: Day) -> String {
+    let names: Vec<String> = (1..=year.event().parts).map(part_name).collect();
+
+    // `solution!` defaults to part one and part two.
+    let parts_option = if names == ["part_one", "part_two"] {
+        String::new()
+    } else {
+        format!(", parts = [{}]", names.join(", "))
+    };
+
+    let functions: Vec<String> = names
+        .iter()
+        .map(|name| PART_TEMPLATE.replace("PART_NAME", name))
+        .collect();
+    let tests: String = names
+        .iter()
+        .map(|name| TEST_TEMPLATE.replace("PART_NAME", name))
+        .collect();
+
+    MODULE_TEMPLATE
+        .replace("DAY_NUMBER", &day.into_inner().to_string())
+        .replace("PARTS_OPTION", &parts_option)
+        .replace("PART_FUNCTIONS", &functions.join("\n"))
+        .replace("PART_TESTS", &tests)
 }
-"#;
 
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+fn create_parent_dir(path: &str) -> Result<(), std::io::Error> {
+    match Path::new(path).parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
+    }
+}
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
+/// Scaffolds each of the days, and exits with an error if any of them failed.
+pub fn handle(year: Year, days: &[Day]) {
+    let mut failed = false;
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+    for (ix, &day) in days.iter().enumerate() {
+        if ix > 0 {
+            println!();
         }
-    }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+        if let Err(e) = scaffold_day(year, day) {
+            eprintln!("{e}");
+            failed = true;
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
+    if failed {
+        process::exit(1);
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    let day = match days {
+        [day] => day.to_string(),
+        _ => "<day>".into(),
+    };
+    if Year::from_env() == Some(year) {
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    } else {
+        println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+    }
+}
+
+fn scaffold_day(year: Year, day: Day) -> Result<(), String> {
+    let input_path = format!("data/{year}/inputs/{day}.txt");
+    let example_path = format!("data/{year}/examples/{day}.txt");
+    let module_path = format!("src/bin/{}.rs", registry::get_bin_name(year, day));
+
+    for path in [&input_path, &example_path] {
+        create_parent_dir(path).map_err(|e| format!("Failed to create data directory: {e}"))?;
+    }
+
+    let mut file = safe_create_file(&module_path)
+        .map_err(|e| format!("Failed to create module file \"{module_path}\": {e}"))?;
+
+    file.write_all(module_contents(year, day).as_bytes())
+        .map_err(|e| format!("Failed to write module contents: {e}"))?;
+    println!("Created module file \"{}\"", &module_path);
+
+    create_file(&input_path).map_err(|e| format!("Failed to create input file: {e}"))?;
+    println!("Created empty input file \"{}\"", &input_path);
+
+    create_file(&example_path).map_err(|e| format!("Failed to create example file: {e}"))?;
+    println!("Created empty example file \"{}\"", &example_path);
+
+    Ok(())
 }
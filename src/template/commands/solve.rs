@@ -1,13 +1,34 @@
 use std::process::{Command, Stdio};
 
-use crate::Day;
+use crate::template::commands::RunOptions;
+use crate::template::input::Input;
+use crate::template::registry::{self, Solution};
+use crate::template::runner;
+use crate::{Day, Year};
+
+pub fn handle(
+    solutions: &[Solution],
+    year: Year,
+    day: Day,
+    options: &RunOptions,
+    submit_part: Option<u8>,
+    input: Option<&Input>,
+) {
+    // the runner reads the options from the arguments of this process, which match those that
+    // would be forwarded to the solution binary.
+    if let Some(solution) = registry::find(solutions, year, day).filter(|_| !options.is_isolated())
+    {
+        runner::run(solution);
+        return;
+    }
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        registry::get_bin_name(year, day),
+    ];
 
-    if release {
-        cmd_args.push("--release".to_string());
-    }
+    cmd_args.append(&mut options.cargo_args());
 
     cmd_args.push("--".to_string());
 
@@ -16,8 +37,10 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    if time {
-        cmd_args.push("--time".to_string());
+    cmd_args.append(&mut options.solution_args());
+
+    if let Some(input) = input {
+        cmd_args.append(&mut input.to_args());
     }
 
     let mut cmd = Command::new("cargo")
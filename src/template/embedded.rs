@@ -0,0 +1,96 @@
+/// Inputs and examples embedded into the binaries with the `embed_inputs` feature.
+///
+/// With the feature, `build.rs` lists the inputs and examples of every solution in
+/// `data/<year>/inputs` and `data/<year>/examples`, and the `solution!` macro embeds those of its
+/// day. The runner reads embedded files before the ones on disk, so that a binary also runs from
+/// another directory or machine. Files that don't exist at build time are read from disk instead.
+use std::sync::Mutex;
+
+use crate::event::str_eq;
+use crate::{Day, Year};
+
+/// An embedded file: its path relative to `data/<year>`, e.g. `inputs/05.txt`, and its contents.
+pub type File = (&'static str, &'static str);
+
+/// The embedded files of each day, as `(prefix, year, day, files)`.
+#[cfg(feature = "embed_inputs")]
+const FILES: &[(&str, u16, u8, &[File])] = include!(concat!(env!("OUT_DIR"), "/embedded.rs"));
+
+#[cfg(not(feature = "embed_inputs"))]
+const FILES: &[(&str, u16, u8, &[File])] = &[];
+
+/// The files of the running day, see [`select`].
+static SELECTED: Mutex<Option<(Year, Day, &[File])>> = Mutex::new(None);
+
+/// The embedded files of a day. Evaluated at compile time by the `solution!` macro, so that a
+/// binary only contains the files of the days it runs.
+#[must_use]
+pub const fn files(year: Year, day: Day) -> &'static [File] {
+    let mut ix = 0;
+    while ix < FILES.len() {
+        let (prefix, number, other_day, files) = FILES[ix];
+        if str_eq(prefix, year.prefix())
+            && number == year.into_inner()
+            && other_day == day.into_inner()
+        {
+            return files;
+        }
+        ix += 1;
+    }
+    &[]
+}
+
+/// Makes the embedded files of a day available to [`read`] and [`list`].
+pub fn select(year: Year, day: Day, files: &'static [File]) {
+    *SELECTED.lock().unwrap() = Some((year, day, files));
+}
+
+/// Reads an embedded file of a day by its path relative to `data/<year>`, if the day is the
+/// [`select`]ed one and the file was embedded.
+#[must_use]
+pub fn read(year: Year, day: Day, path: &str) -> Option<&'static str> {
+    list(year, day, path)
+        .into_iter()
+        .find(|(other, _)| *other == path)
+        .map(|(_, contents)| contents)
+}
+
+/// The embedded files of a day whose path starts with `prefix`, e.g. `examples/05/`.
+#[must_use]
+pub fn list(year: Year, day: Day, prefix: &str) -> Vec<File> {
+    match *SELECTED.lock().unwrap() {
+        Some((selected_year, selected_day, files))
+            if (selected_year, selected_day) == (year, day) =>
+        {
+            files
+                .iter()
+                .filter(|(path, _)| path.starts_with(prefix))
+                .copied()
+                .collect()
+        }
+        _ => vec![],
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{list, read, select, File};
+    use crate::{day, year};
+
+    #[test]
+    fn reads_files_of_the_selected_day() {
+        static FILES: &[File] = &[
+            ("inputs/05.txt", "input"),
+            ("examples/05/a.txt", "a"),
+            ("examples/05/a.answers", "1: 2"),
+        ];
+        select(year!(2015), day!(5), FILES);
+
+        assert_eq!(read(year!(2015), day!(5), "inputs/05.txt"), Some("input"));
+        assert_eq!(read(year!(2015), day!(5), "examples/05.txt"), None);
+        assert_eq!(read(year!(2015), day!(6), "inputs/05.txt"), None);
+        assert_eq!(list(year!(2015), day!(5), "examples/05/").len(), 2);
+    }
+}
@@ -0,0 +1,362 @@
+/// Named examples of a day with their expected answers.
+///
+/// The examples of a day live in `data/<year>/examples/<day>/`. Each example is an input file
+/// `<name>.txt`, optionally accompanied by `<name>.answers` with the expected answers in the
+/// format of the [answers file](crate::template::answers):
+///
+/// ```text
+/// data/2023/examples/08/
+///   simple.txt
+///   simple.answers     # 1: 2
+///   repeating.txt
+///   repeating.answers  # 1: 6
+///   ghosts.txt
+///   ghosts.answers     # 2: 6
+/// ```
+use std::{fmt::Debug, fs, path::PathBuf};
+
+use crate::template::answers::{Answers, Error};
+use crate::template::solution::{Answer, PartResult};
+use crate::template::{embedded, get_data_path};
+use crate::{Day, Year};
+
+/// An example input and the answers it is expected to produce.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub answers: Answers,
+}
+
+impl Example {
+    /// Asserts that `solve` returns the expected answer of `part` for this example.
+    /// Does nothing if the example has no expected answer for the part.
+    ///
+    /// ```ignore
+    /// for example in read_examples(YEAR, DAY).unwrap() {
+    ///     example.assert_part(1, part_one);
+    /// }
+    /// ```
+    pub fn assert_part<R: PartResult>(&self, part: u8, solve: impl FnOnce(&str) -> R) {
+        let Some(expected) = self.answers.get(&part.to_string()) else {
+            return;
+        };
+
+        match solve(&self.input).to_answer() {
+            Answer::Solved(answer) => assert_eq!(
+                answer, expected,
+                "part {part} of example `{}` has the wrong answer",
+                self.name
+            ),
+            answer => panic!(
+                "part {part} of example `{}` expected {expected}, got {answer:?}",
+                self.name
+            ),
+        }
+    }
+}
+
+/// Reads all examples of a day, ordered by name. A day without examples folder has none.
+/// [Embedded](embedded) examples take the place of the examples folder.
+pub fn read_examples(year: Year, day: Day) -> Result<Vec<Example>, Error> {
+    let embedded_examples = read_embedded_examples(year, day)?;
+    if !embedded_examples.is_empty() {
+        return Ok(embedded_examples);
+    }
+
+    let folder = get_examples_path(year, day);
+    if !folder.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut examples = vec![];
+
+    for entry in fs::read_dir(&folder)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "txt") {
+            continue;
+        }
+
+        let Some(name) = path.file_stem().map(|s| s.to_string_lossy().into_owned()) else {
+            continue;
+        };
+
+        examples.push(Example {
+            input: fs::read_to_string(&path)?,
+            answers: Answers::read_from(&path.with_extension("answers"))?,
+            name,
+        });
+    }
+
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(examples)
+}
+
+/// Reads the examples of a day that were [embedded](embedded), ordered by name.
+fn read_embedded_examples(year: Year, day: Day) -> Result<Vec<Example>, Error> {
+    let folder = format!("examples/{day}/");
+    let files = embedded::list(year, day, &folder);
+
+    let mut examples = vec![];
+
+    for (path, input) in &files {
+        let Some(name) = path
+            .strip_prefix(folder.as_str())
+            .and_then(|name| name.strip_suffix(".txt"))
+        else {
+            continue;
+        };
+
+        let answers_path = format!("{folder}{name}.answers");
+        let answers = match files.iter().find(|(path, _)| *path == answers_path) {
+            Some((_, answers)) => answers.parse()?,
+            None => Answers::default(),
+        };
+
+        examples.push(Example {
+            name: name.to_string(),
+            input: (*input).to_string(),
+            answers,
+        });
+    }
+
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(examples)
+}
+
+/// Reads the input of a single named example.
+///
+/// # Panics
+///
+/// If the example doesn't exist.
+#[must_use]
+pub fn read_example(year: Year, day: Day, name: &str) -> String {
+    if let Some(input) = embedded::read(year, day, &format!("examples/{day}/{name}.txt")) {
+        return input.to_string();
+    }
+
+    let path = get_examples_path(year, day).join(format!("{name}.txt"));
+    fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("could not open example \"{}\": {e}", path.display()))
+}
+
+#[must_use]
+pub fn get_examples_path(year: Year, day: Day) -> PathBuf {
+    get_data_path(year).join("examples").join(day.to_string())
+}
+
+/// Asserts that a part returned the expected answer for an example, see
+/// [`solution_tests!`](crate::solution_tests). On failure, the message shows the example's input
+/// with the expected and the actual answer.
+#[track_caller]
+pub fn assert_example<T: PartialEq + Debug>(
+    part: &str,
+    example: &str,
+    input: &str,
+    expected: &T,
+    actual: &T,
+) {
+    assert!(
+        expected == actual,
+        "{part} has the wrong answer for {example}\n  expected: {expected:?}\n    actual: {actual:?}\n\n{input}"
+    );
+}
+
+/// Generates the example tests of a day from the expected answer of each part.
+///
+/// Every part is followed by its expected answer on the day's example file, or by a block of
+/// named [examples](self) with their expected answers. A part without an expected answer, or
+/// one that expects `None`, is ignored with a visible reason instead of passing. The `parse` and
+/// `input` options take the same values as in [`solution!`](crate::solution) and come first.
+/// A part whose function was renamed in `solution!` names it the same way, e.g.
+/// `part_one = fast::part_one: Some(35)`.
+///
+/// ```ignore
+/// advent_of_code::solution_tests! {
+///     parse = parse,
+///     part_one = part_one_fast: Some(35),
+///     part_two: { simple: Some(2), ghosts: Some(6) },
+///     part_three: ignore "needs a larger example",
+/// }
+/// ```
+///
+/// The tests are named after the part and the example, e.g. `solution_tests::part_one::example`.
+#[macro_export]
+macro_rules! solution_tests {
+    ($($tests:tt)*) => {
+        #[cfg(test)]
+        mod solution_tests {
+            use super::*;
+
+            $crate::__solution_tests!([] []; $($tests)*);
+        }
+    };
+}
+
+// Not part of the public API: helpers of `solution_tests!`.
+
+/// Munches the options and then generates a module with the tests of each part.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __solution_tests {
+    ([$($parse:tt)*] [$($input:tt)*]; $(,)?) => {};
+    ([$($parse:tt)*] [$($input:tt)*]; parse = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__solution_tests!([$value] [$($input)*]; $($($rest)*)?);
+    };
+    ([$($parse:tt)*] [$($input:tt)*]; input = $value:ty $(, $($rest:tt)*)?) => {
+        $crate::__solution_tests!([$($parse)*] [$value]; $($($rest)*)?);
+    };
+    ([$($parse:tt)*] [$($input:tt)*]; $part:ident = $func:path: $($rest:tt)*) => {
+        $crate::__solution_tests!(@part [$($parse)*] [$($input)*] $part [$func]; $($rest)*);
+    };
+    ([$($parse:tt)*] [$($input:tt)*]; $part:ident: $($rest:tt)*) => {
+        $crate::__solution_tests!(@part [$($parse)*] [$($input)*] $part [$part]; $($rest)*);
+    };
+    ([$($parse:tt)*] [$($input:tt)*]; $part:ident $(, $($rest:tt)*)?) => {
+        $crate::__solution_tests!(
+            [$($parse)*] [$($input)*];
+            $part: ignore "no expected answer for the example" $(, $($rest)*)?
+        );
+    };
+    // the tests of a part, named `$part`, which call the function `$func`.
+    (@part [$($parse:tt)*] [$($input:tt)*] $part:ident [$func:path]; ignore $reason:literal $(, $($rest:tt)*)?) => {
+        mod $part {
+            #[test]
+            #[ignore = $reason]
+            fn example() {}
+        }
+        $crate::__solution_tests!([$($parse)*] [$($input)*]; $($($rest)*)?);
+    };
+    (@part [$($parse:tt)*] [$($input:tt)*] $part:ident [$func:path]; None $(, $($rest:tt)*)?) => {
+        $crate::__solution_tests!(
+            @part [$($parse)*] [$($input)*] $part [$func];
+            ignore "expects `None`, the part is not solved yet" $(, $($rest)*)?
+        );
+    };
+    (@part [$($parse:tt)*] [$($input:tt)*] $part:ident [$func:path]; { $($example:ident: $expected:expr),+ $(,)? } $(, $($rest:tt)*)?) => {
+        mod $part {
+            use super::*;
+
+            $crate::__solution_tests_named!([$($parse)*] [$($input)*] $part [$func]; $($example: $expected),+);
+        }
+        $crate::__solution_tests!([$($parse)*] [$($input)*]; $($($rest)*)?);
+    };
+    (@part [$($parse:tt)*] [$($input:tt)*] $part:ident [$func:path]; $expected:expr $(, $($rest:tt)*)?) => {
+        mod $part {
+            use super::*;
+
+            #[test]
+            fn example() {
+                let input = $crate::template::read_file("examples", YEAR, DAY);
+                $crate::__solution_tests_check!(
+                    $part, $func, "the example", input, $expected;
+                    [$($parse)*] [$($input)*]
+                );
+            }
+        }
+        $crate::__solution_tests!([$($parse)*] [$($input)*]; $($($rest)*)?);
+    };
+}
+
+/// Generates a test per named example of a part.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __solution_tests_named {
+    ([$($parse:tt)*] [$($input:tt)*] $part:ident [$func:path];) => {};
+    ([$($parse:tt)*] [$($input:tt)*] $part:ident [$func:path]; $example:ident: $expected:expr $(, $($rest:tt)*)?) => {
+        #[test]
+        fn $example() {
+            let input = $crate::template::examples::read_example(YEAR, DAY, stringify!($example));
+            $crate::__solution_tests_check!(
+                $part, $func, concat!("example `", stringify!($example), "`"), input, $expected;
+                [$($parse)*] [$($input)*]
+            );
+        }
+        $crate::__solution_tests_named!([$($parse)*] [$($input)*] $part [$func]; $($($rest)*)?);
+    };
+}
+
+/// Runs a part on an example input the way `solution!` does, and asserts its answer.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __solution_tests_check {
+    ($part:ident, $func:path, $example:expr, $input:ident, $expected:expr; [$($parse:tt)*] [$($ty:tt)*]) => {
+        // inputs are `'static` in solutions, see `FromInput`.
+        let input: &'static str = Box::leak($input.into_boxed_str());
+        let actual = $func($crate::__solution_tests_arg!(input; [$($parse)*] [$($ty)*]));
+        $crate::template::examples::assert_example(
+            stringify!($part),
+            $example,
+            input,
+            &$expected,
+            &actual,
+        );
+    };
+}
+
+/// The argument of a part: the input, its conversion and/or a reference to its parsed value.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __solution_tests_arg {
+    ($input:ident; [] []) => {
+        $input
+    };
+    ($input:ident; [$parse:expr] []) => {
+        &$parse($input)
+    };
+    ($input:ident; [] [$ty:ty]) => {
+        <$ty as $crate::template::solution::FromInput>::from_input($input)
+    };
+    ($input:ident; [$parse:expr] [$ty:ty]) => {
+        &$parse(<$ty as $crate::template::solution::FromInput>::from_input(
+            $input,
+        ))
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{assert_example, Example};
+
+    fn example() -> Example {
+        Example {
+            name: "simple".into(),
+            input: "1 2 3".into(),
+            answers: "1: 6".parse().unwrap(),
+        }
+    }
+
+    fn sum(input: &str) -> Option<u32> {
+        input.split(' ').map(|n| n.parse::<u32>().ok()).sum()
+    }
+
+    #[test]
+    fn passes_expected_answers() {
+        example().assert_part(1, sum);
+    }
+
+    #[test]
+    fn skips_parts_without_answers() {
+        example().assert_part(2, |_| -> Option<u32> { unreachable!() });
+    }
+
+    #[test]
+    #[should_panic(expected = "part 1 of example `simple` has the wrong answer")]
+    fn fails_wrong_answers() {
+        example().assert_part(1, |_| Some(7));
+    }
+
+    #[test]
+    #[should_panic(expected = "part 1 of example `simple` expected 6, got Unsolved")]
+    fn fails_unsolved_parts() {
+        example().assert_part(1, |_| None::<u32>);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "part_one has the wrong answer for the example\n  expected: Some(6)\n    actual: Some(7)\n\n1 2 3"
+    )]
+    fn shows_failed_examples() {
+        assert_example("part_one", "the example", "1 2 3", &Some(6), &Some(7));
+    }
+}
@@ -0,0 +1,292 @@
+//! Mutations of puzzle inputs for the `fuzz` command, which looks for inputs that make a
+//! solution panic or hang.
+//!
+//! Inputs are mutated the way they tend to differ from what a parser expects: line endings,
+//! surrounding whitespace, missing or extra blank lines, truncation and changed digits. Random
+//! mutations are deterministic for a seed, so that a run can be repeated.
+
+/// A mutated input and how it was derived.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mutant {
+    pub description: String,
+    pub input: String,
+}
+
+/// The mutations that apply to a whole input, e.g. CRLF line endings. Mutations that leave the
+/// input unchanged are left out.
+#[must_use]
+pub fn systematic(input: &str) -> Vec<Mutant> {
+    let map_lines = |func: &dyn Fn(&str) -> String| -> String {
+        input
+            .split_inclusive('\n')
+            .map(|line| match line.strip_suffix('\n') {
+                Some(line) => func(line) + "\n",
+                None => func(line),
+            })
+            .collect()
+    };
+
+    let mutants = [
+        ("CRLF line endings", input.replace('\n', "\r\n")),
+        (
+            "no trailing newline",
+            input.trim_end_matches('\n').to_string(),
+        ),
+        ("extra trailing newline", format!("{input}\n")),
+        ("trailing spaces", map_lines(&|line| format!("{line} "))),
+        ("leading spaces", map_lines(&|line| format!(" {line}"))),
+        ("tabs for spaces", input.replace(' ', "\t")),
+        (
+            "no blank lines",
+            input
+                .split_inclusive('\n')
+                .filter(|line| !line.trim().is_empty())
+                .collect(),
+        ),
+        ("empty input", String::new()),
+    ];
+
+    let mut result: Vec<Mutant> = vec![];
+    for (description, mutated) in mutants {
+        if mutated != input && result.iter().all(|mutant| mutant.input != mutated) {
+            result.push(Mutant {
+                description: description.into(),
+                input: mutated,
+            });
+        }
+    }
+    result
+}
+
+/// A small random change of the input, e.g. a dropped line or a changed digit.
+#[must_use]
+pub fn random(input: &str, rng: &mut Rng) -> Mutant {
+    let chars: Vec<char> = input.chars().collect();
+    let lines: Vec<&str> = input.split_inclusive('\n').collect();
+    let digits: Vec<usize> = (0..chars.len())
+        .filter(|&ix| chars[ix].is_ascii_digit())
+        .collect();
+
+    if chars.is_empty() {
+        return Mutant {
+            description: "inserted a newline".into(),
+            input: "\n".into(),
+        };
+    }
+
+    let mutant = |description: String, chars: Vec<char>| Mutant {
+        description,
+        input: chars.into_iter().collect(),
+    };
+
+    match rng.below(7) {
+        0 => {
+            let at = rng.below(chars.len());
+            mutant(
+                format!("truncated after {at} characters"),
+                chars[..at].to_vec(),
+            )
+        }
+        1 => {
+            let ix = rng.below(lines.len());
+            let mut lines = lines;
+            lines.remove(ix);
+            Mutant {
+                description: format!("dropped line {}", ix + 1),
+                input: lines.concat(),
+            }
+        }
+        2 => {
+            let ix = rng.below(lines.len());
+            let mut lines = lines;
+            lines.insert(ix, lines[ix]);
+            Mutant {
+                description: format!("duplicated line {}", ix + 1),
+                input: lines.concat(),
+            }
+        }
+        3 if !digits.is_empty() => {
+            let at = digits[rng.below(digits.len())];
+            let mut chars = chars;
+            let digit = char::from(b'0' + (chars[at] as u8 - b'0' + 1 + rng.below(9) as u8) % 10);
+            chars[at] = digit;
+            let position = position(input, at);
+            mutant(format!("changed a digit to {digit} at {position}"), chars)
+        }
+        4 if !digits.is_empty() => {
+            let at = digits[rng.below(digits.len())];
+            let mut chars = chars;
+            chars.insert(at, '9');
+            let position = position(input, at);
+            mutant(format!("inserted a digit at {position}"), chars)
+        }
+        5 => {
+            let at = rng.below(chars.len());
+            let mut chars = chars;
+            chars.remove(at);
+            let position = position(input, at);
+            mutant(format!("dropped a character at {position}"), chars)
+        }
+        _ => {
+            let at = rng.below(chars.len() + 1);
+            let mut chars = chars;
+            chars.insert(at, ' ');
+            let position = position(input, at);
+            mutant(format!("inserted a space at {position}"), chars)
+        }
+    }
+}
+
+/// Shrinks an input for as long as it still `fails`, by removing chunks of lines and then of
+/// characters. Gives up after checking `budget` candidates and returns the smallest failing
+/// input found so far.
+pub fn minimise(input: &str, budget: usize, mut fails: impl FnMut(&str) -> bool) -> String {
+    let mut budget = budget;
+
+    let lines: Vec<String> = input.split_inclusive('\n').map(String::from).collect();
+    let input = remove_chunks(lines, &mut budget, &mut fails).concat();
+
+    let chars: Vec<String> = input.chars().map(String::from).collect();
+    remove_chunks(chars, &mut budget, &mut fails).concat()
+}
+
+/// Removes chunks of units that `fails` does not depend on, halving the chunk size down to
+/// single units.
+fn remove_chunks(
+    mut units: Vec<String>,
+    budget: &mut usize,
+    fails: &mut impl FnMut(&str) -> bool,
+) -> Vec<String> {
+    let mut chunk = (units.len() / 2).max(1);
+
+    loop {
+        let mut ix = 0;
+        while ix < units.len() {
+            if *budget == 0 {
+                return units;
+            }
+            *budget -= 1;
+
+            let end = (ix + chunk).min(units.len());
+            let candidate = [&units[..ix], &units[end..]].concat();
+            if fails(&candidate.concat()) {
+                units = candidate;
+            } else {
+                ix += chunk;
+            }
+        }
+
+        if chunk == 1 {
+            return units;
+        }
+        chunk /= 2;
+    }
+}
+
+/// The line and column of a character, e.g. `line 3, column 7`.
+fn position(input: &str, char_ix: usize) -> String {
+    let before: Vec<char> = input.chars().take(char_ix).collect();
+    let line = before.iter().filter(|&&c| c == '\n').count() + 1;
+    let column = before.iter().rev().take_while(|&&c| c != '\n').count() + 1;
+    format!("line {line}, column {column}")
+}
+
+/// A xorshift pseudo-random number generator, see <https://www.jstatsoft.org/v08/i14/paper>.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        // the state must not be zero.
+        Self((seed ^ 0x9E37_79B9_7F4A_7C15).max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number in `0..n`, where `n` must not be zero.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{minimise, position, random, systematic, Rng};
+
+    #[test]
+    fn mutates_whole_inputs() {
+        let mutants = systematic("1 2\n\n3\n");
+        let find = |description: &str| {
+            mutants
+                .iter()
+                .find(|mutant| mutant.description == description)
+                .map(|mutant| mutant.input.as_str())
+        };
+
+        assert_eq!(find("CRLF line endings"), Some("1 2\r\n\r\n3\r\n"));
+        assert_eq!(find("no trailing newline"), Some("1 2\n\n3"));
+        assert_eq!(find("trailing spaces"), Some("1 2 \n \n3 \n"));
+        assert_eq!(find("no blank lines"), Some("1 2\n3\n"));
+        assert_eq!(find("empty input"), Some(""));
+    }
+
+    #[test]
+    fn skips_mutations_without_effect() {
+        let mutants = systematic("abc");
+        assert!(mutants.iter().all(|mutant| mutant.input != "abc"));
+        assert!(!mutants
+            .iter()
+            .any(|mutant| mutant.description == "no trailing newline"));
+    }
+
+    #[test]
+    fn mutates_deterministically() {
+        let input = "467..114..\n...*......\n..35..633.\n";
+        let mutate = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..20).map(|_| random(input, &mut rng)).collect::<Vec<_>>()
+        };
+
+        assert_eq!(mutate(1), mutate(1));
+        assert_ne!(mutate(1), mutate(2));
+        assert!(mutate(1).iter().all(|mutant| mutant.input != input));
+    }
+
+    #[test]
+    fn minimises_failing_inputs() {
+        let input = "abc\ndef\nxyz\nghi\n";
+        assert_eq!(minimise(input, 1000, |input| input.contains('y')), "y");
+        assert_eq!(
+            minimise(input, 1000, |input| input.contains('d')
+                && input.contains('h')),
+            "dh"
+        );
+    }
+
+    #[test]
+    fn stops_minimising_at_budget() {
+        let input = "abc\ndef\nxyz\nghi\n";
+        let mut checks = 0;
+        let minimised = minimise(input, 3, |input| {
+            checks += 1;
+            input.contains('y')
+        });
+
+        assert_eq!(checks, 3);
+        assert!(minimised.contains('y'));
+    }
+
+    #[test]
+    fn describes_positions() {
+        assert_eq!(position("ab\ncd", 0), "line 1, column 1");
+        assert_eq!(position("ab\ncd", 4), "line 2, column 2");
+    }
+}
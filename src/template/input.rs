@@ -0,0 +1,212 @@
+/// Selection of the puzzle input a solution runs on.
+///
+/// By default, a solution reads `data/<year>/inputs/<day>.txt`. The input can be replaced on the
+/// command line with `--input <path>`, `--input -` (stdin) or `--input-str <text>`, or the
+/// solution can be run on each of its [examples](crate::template::examples) with `--examples`.
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::{embedded, get_data_path};
+use crate::{Day, Year};
+
+/// An input given on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    /// `--input <path>`
+    File(PathBuf),
+    /// `--input -`
+    Stdin,
+    /// `--input-str <text>`, where `\n` stands for a line break.
+    Inline(String),
+    /// `--examples`, runs the solution on each example of the day.
+    Examples,
+}
+
+impl Input {
+    /// Reads the input from command-line arguments, if one is given.
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Option<Self>, pico_args::Error> {
+        let path: Option<String> = args.opt_value_from_str("--input")?;
+        let text: Option<String> = args.opt_value_from_str("--input-str")?;
+        let examples = args.contains("--examples");
+
+        match (path, text, examples) {
+            (Some(path), None, false) if path == "-" => Ok(Some(Input::Stdin)),
+            (Some(path), None, false) => Ok(Some(Input::File(path.into()))),
+            (None, Some(text), false) => Ok(Some(Input::Inline(text.replace("\\n", "\n")))),
+            (None, None, true) => Ok(Some(Input::Examples)),
+            (None, None, false) => Ok(None),
+            _ => Err(pico_args::Error::ArgumentParsingFailed {
+                cause: "only one of `--input`, `--input-str` and `--examples` can be given".into(),
+            }),
+        }
+    }
+
+    /// The input as arguments for a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Input::File(path) => vec!["--input".into(), path.to_string_lossy().into_owned()],
+            Input::Stdin => vec!["--input".into(), "-".into()],
+            Input::Inline(text) => vec!["--input-str".into(), text.clone()],
+            Input::Examples => vec!["--examples".into()],
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Input::File(path) => fs::read_to_string(path),
+            Input::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            Input::Inline(text) => Ok(text.clone()),
+            Input::Examples => Err(io::Error::other(
+                "examples are read one at a time with `read_examples`",
+            )),
+        }
+    }
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Input::File(path) => write!(f, "\"{}\"", resolve(path).display()),
+            Input::Stdin => write!(f, "from stdin"),
+            Input::Inline(_) => write!(f, "from `--input-str`"),
+            Input::Examples => write!(f, "from the examples"),
+        }
+    }
+}
+
+/// The input selected on the command line of the running solution binary.
+/// Exits with an error message if the arguments are invalid.
+#[must_use]
+pub fn selected() -> Option<Input> {
+    Input::from_args(&mut pico_args::Arguments::from_env()).unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    })
+}
+
+/// Reads the input of the running solution binary. Exits with an error message if the input
+/// can't be read.
+#[must_use]
+pub fn load(year: Year, day: Day) -> String {
+    try_load(year, day).unwrap_or_else(|message| {
+        eprintln!("{message}");
+        process::exit(1);
+    })
+}
+
+/// Reads the input of the running solution binary, see [`load`].
+pub fn try_load(year: Year, day: Day) -> Result<String, String> {
+    let Some(input) = selected() else {
+        return read_data_file("inputs", year, day);
+    };
+
+    input
+        .read()
+        .map_err(|e| format!("Could not read input {input}: {e}"))
+}
+
+/// Whether the running solution binary was given an input on the command line, in which case
+/// the accepted answers of the day don't apply.
+#[must_use]
+pub fn is_custom() -> bool {
+    env::args().any(|arg| {
+        ["--input", "--input-str", "--examples"]
+            .iter()
+            .any(|flag| arg == *flag || arg.starts_with(&format!("{flag}=")))
+    })
+}
+
+/// Reads `data/<year>/<folder>/<day>.txt`, or its [embedded](embedded) copy. The error names the
+/// resolved path and how to create it.
+pub fn read_data_file(folder: &str, year: Year, day: Day) -> Result<String, String> {
+    if let Some(contents) = embedded::read(year, day, &format!("{folder}/{day}.txt")) {
+        return Ok(contents.to_string());
+    }
+
+    let path = resolve(&get_data_path(year).join(folder).join(format!("{day}.txt")));
+
+    fs::read_to_string(&path).map_err(|e| {
+        let hint = match folder {
+            "inputs" => format!(
+                "Run `cargo download {day} --year {year}` to download it, \
+                 or `cargo scaffold {day} --year {year}` to create an empty one."
+            ),
+            _ => format!("Run `cargo scaffold {day} --year {year}` to create an empty one."),
+        };
+        format!(
+            "Could not open {} file \"{}\": {e}\n{hint}",
+            folder.trim_end_matches('s'),
+            path.display()
+        )
+    })
+}
+
+fn resolve(path: &Path) -> PathBuf {
+    env::current_dir().map_or_else(|_| path.to_path_buf(), |cwd| cwd.join(path))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{read_data_file, Input};
+    use crate::{day, year};
+
+    fn args(args: &[&str]) -> pico_args::Arguments {
+        pico_args::Arguments::from_vec(args.iter().map(Into::into).collect())
+    }
+
+    #[test]
+    fn parses_input_args() {
+        assert_eq!(Input::from_args(&mut args(&[])).unwrap(), None);
+        assert_eq!(
+            Input::from_args(&mut args(&["--input", "in.txt"])).unwrap(),
+            Some(Input::File("in.txt".into()))
+        );
+        assert_eq!(
+            Input::from_args(&mut args(&["--input", "-"])).unwrap(),
+            Some(Input::Stdin)
+        );
+        assert_eq!(
+            Input::from_args(&mut args(&["--input-str", "LR\\n\\nAAA"])).unwrap(),
+            Some(Input::Inline("LR\n\nAAA".into()))
+        );
+    }
+
+    #[test]
+    fn rejects_combined_inputs() {
+        assert!(Input::from_args(&mut args(&["--input", "-", "--input-str", "1"])).is_err());
+        assert!(Input::from_args(&mut args(&["--input", "-", "--examples"])).is_err());
+    }
+
+    #[test]
+    fn round_trips_args() {
+        for input in [
+            Input::File("in.txt".into()),
+            Input::Stdin,
+            Input::Inline("1 2".into()),
+            Input::Examples,
+        ] {
+            let mut parsed = pico_args::Arguments::from_vec(
+                input.to_args().into_iter().map(Into::into).collect(),
+            );
+            assert_eq!(Input::from_args(&mut parsed).unwrap(), Some(input));
+        }
+    }
+
+    #[test]
+    fn explains_missing_files() {
+        let message = read_data_file("inputs", year!(2015), day!(25)).unwrap_err();
+        assert!(message.contains("data/2015/inputs/25.txt"));
+        assert!(message.contains("cargo download 25 --year 2015"));
+    }
+}
@@ -1,10 +1,28 @@
-use crate::Day;
-use std::{env, fs};
+use std::path::PathBuf;
 
+use crate::{Day, Year};
+
+pub mod alloc;
+pub mod answers;
 pub mod aoc_cli;
+pub mod bench;
 pub mod commands;
+pub mod embedded;
+pub mod examples;
+pub mod fuzz;
+pub mod input;
+pub mod ocr;
+pub mod progress;
 pub mod readme_benchmarks;
+pub mod registry;
+pub mod report;
 pub mod runner;
+pub mod solution;
+pub mod stats;
+pub mod trace;
+pub mod unwind;
+pub mod visualize;
+pub mod watchdog;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -12,25 +30,287 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file(folder: &str, year: Year, day: Day) -> String {
+    input::read_data_file(folder, year, day).unwrap_or_else(|message| panic!("{message}"))
+}
+
+/// The folder that holds the data of a year: `data/<year>`.
+#[must_use]
+pub fn get_data_path(year: Year) -> PathBuf {
+    PathBuf::from(format!("data/{year}"))
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `YEAR` and `DAY` and sets up the input and runner for each part. The
+/// day's [`Solution`](registry::Solution) is declared as `SOLUTION`, which the main binary runs
+/// in-process.
+///
+/// The year is read from the name of the solution file, `src/bin/<year>-<day>.rs`, and the day
+/// must be part of that year's [`Event`](crate::Event), with as many parts as the event's days.
+///
+/// The macro accepts options as `key = value` pairs after the day:
+///  - `parse = parse_fn`: declares a parse stage. The runner times `parse_fn(&input)` separately
+///    and passes a reference to the parsed value to both parts.
+///  - `input = Type`: converts the input with [`FromInput`](solution::FromInput), e.g. to bytes,
+///    lines or a grid. The conversion is timed as the parse stage, and the parts take the
+///    converted value. With `parse`, the parse function takes it instead.
+///  - `part_one = path`, `part_two = path`: the functions of the parts, by default `part_one`
+///    and `part_two`.
+///  - `parts = [path, ...]`: the functions of all parts, for events whose days don't have two
+///    parts, e.g. `parts = [part_one, part_two, part_three]` for Everybody Codes. A single part,
+///    e.g. `parts = [part_one]`, declares a single-part day like the last day of Advent of Code,
+///    whose other parts show as `n/a`.
+///  - `extra = [name: path, ...]`: further parts that take the same input. The runner prints them
+///    by name after the two parts.
+///  - `variants = [part: [name: path, ...], ...]`: alternative implementations of a part by its
+///    number. `--variant <name>` runs a variant in place of its part, `--variants` runs all of them
+///    next to the part, checks that they agree with it and compares their timings.
+///  - `timeout = "60s"`: overrides the time limit of each stage for this day.
+///
+/// ```ignore
+/// advent_of_code::solution!(5, parse = parse_almanac, timeout = "2m");
+/// advent_of_code::solution!(6, input = Vec<&'static str>, extra = [naive: part_two_naive]);
+/// advent_of_code::solution!(6, variants = [2: [closed_form: part_two_closed_form]]);
+/// ```
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    ($day:expr $(, $($options:tt)*)?) => {
+        $crate::__solution_options!(
+            [$day] [] [] [part_one] [part_two] [] [] [] [];
+            $($($options)*)?
+        );
+    };
+}
+
+// Not part of the public API: helpers that resolve the options of `solution!`.
+
+/// Collects the options into slots: day, parse, input, part one, part two, parts, timeout, extra
+/// parts and variants. Once all options are consumed, the slots are passed on to `__solution_impl!`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __solution_options {
+    (
+        [$($day:tt)*] [$($parse:tt)*] [$($input:tt)*] [$($one:tt)*] [$($two:tt)*] [$($parts:tt)*] [$($timeout:tt)*] [$($extra:tt)*] [$($variants:tt)*];
+        $(,)?
+    ) => {
+        $crate::__solution_impl!(
+            [$($day)*] [$($parse)*] [$($input)*] [$($one)*] [$($two)*] [$($parts)*] [$($timeout)*] [$($extra)*] [$($variants)*]
+        );
+    };
+    (
+        [$($day:tt)*] [$($parse:tt)*] [$($input:tt)*] [$($one:tt)*] [$($two:tt)*] [$($parts:tt)*] [$($timeout:tt)*] [$($extra:tt)*] [$($variants:tt)*];
+        parse = $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::__solution_options!(
+            [$($day)*] [$value] [$($input)*] [$($one)*] [$($two)*] [$($parts)*] [$($timeout)*] [$($extra)*] [$($variants)*];
+            $($($rest)*)?
+        );
+    };
+    (
+        [$($day:tt)*] [$($parse:tt)*] [$($input:tt)*] [$($one:tt)*] [$($two:tt)*] [$($parts:tt)*] [$($timeout:tt)*] [$($extra:tt)*] [$($variants:tt)*];
+        input = $value:ty $(, $($rest:tt)*)?
+    ) => {
+        $crate::__solution_options!(
+            [$($day)*] [$($parse)*] [$value] [$($one)*] [$($two)*] [$($parts)*] [$($timeout)*] [$($extra)*] [$($variants)*];
+            $($($rest)*)?
+        );
+    };
+    (
+        [$($day:tt)*] [$($parse:tt)*] [$($input:tt)*] [$($one:tt)*] [$($two:tt)*] [$($parts:tt)*] [$($timeout:tt)*] [$($extra:tt)*] [$($variants:tt)*];
+        part_one = $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::__solution_options!(
+            [$($day)*] [$($parse)*] [$($input)*] [$value] [$($two)*] [$($parts)*] [$($timeout)*] [$($extra)*] [$($variants)*];
+            $($($rest)*)?
+        );
+    };
+    (
+        [$($day:tt)*] [$($parse:tt)*] [$($input:tt)*] [$($one:tt)*] [$($two:tt)*] [$($parts:tt)*] [$($timeout:tt)*] [$($extra:tt)*] [$($variants:tt)*];
+        part_two = $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::__solution_options!(
+            [$($day)*] [$($parse)*] [$($input)*] [$($one)*] [$value] [$($parts)*] [$($timeout)*] [$($extra)*] [$($variants)*];
+            $($($rest)*)?
+        );
+    };
+    (
+        [$($day:tt)*] [$($parse:tt)*] [$($input:tt)*] [$($one:tt)*] [$($two:tt)*] [$($parts:tt)*] [$($timeout:tt)*] [$($extra:tt)*] [$($variants:tt)*];
+        parts = [$($part:path),+ $(,)?] $(, $($rest:tt)*)?
+    ) => {
+        $crate::__solution_options!(
+            [$($day)*] [$($parse)*] [$($input)*] [$($one)*] [$($two)*] [$($part),+] [$($timeout)*] [$($extra)*] [$($variants)*];
+            $($($rest)*)?
+        );
+    };
+    (
+        [$($day:tt)*] [$($parse:tt)*] [$($input:tt)*] [$($one:tt)*] [$($two:tt)*] [$($parts:tt)*] [$($timeout:tt)*] [$($extra:tt)*] [$($variants:tt)*];
+        timeout = $value:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::__solution_options!(
+            [$($day)*] [$($parse)*] [$($input)*] [$($one)*] [$($two)*] [$($parts)*] [$value] [$($extra)*] [$($variants)*];
+            $($($rest)*)?
+        );
+    };
+    (
+        [$($day:tt)*] [$($parse:tt)*] [$($input:tt)*] [$($one:tt)*] [$($two:tt)*] [$($parts:tt)*] [$($timeout:tt)*] [$($extra:tt)*] [$($variants:tt)*];
+        extra = [$($name:ident : $func:path),* $(,)?] $(, $($rest:tt)*)?
+    ) => {
+        $crate::__solution_options!(
+            [$($day)*] [$($parse)*] [$($input)*] [$($one)*] [$($two)*] [$($parts)*] [$($timeout)*] [$($name: $func),*] [$($variants)*];
+            $($($rest)*)?
+        );
+    };
+    (
+        [$($day:tt)*] [$($parse:tt)*] [$($input:tt)*] [$($one:tt)*] [$($two:tt)*] [$($parts:tt)*] [$($timeout:tt)*] [$($extra:tt)*] [$($variants:tt)*];
+        variants = [$($part:literal : [$($name:ident : $func:path),* $(,)?]),* $(,)?] $(, $($rest:tt)*)?
+    ) => {
+        $crate::__solution_options!(
+            [$($day)*] [$($parse)*] [$($input)*] [$($one)*] [$($two)*] [$($parts)*] [$($timeout)*] [$($extra)*] [$($part: [$($name: $func),*]),*];
+            $($($rest)*)?
+        );
+    };
+    (
+        [$($day:tt)*] [$($parse:tt)*] [$($input:tt)*] [$($one:tt)*] [$($two:tt)*] [$($parts:tt)*] [$($timeout:tt)*] [$($extra:tt)*] [$($variants:tt)*];
+        $other:ident $($rest:tt)*
+    ) => {
+        compile_error!(concat!(
+            "unknown option `",
+            stringify!($other),
+            "` in `solution!`, expecting one of: parse, input, part_one, part_two, parts, extra, variants, timeout"
+        ));
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __solution_impl {
+    (
+        [$day:expr] [$($parse:expr)?] [$($input:ty)?] [$part_one:expr] [$part_two:expr]
+        [$($parts:path),*] [$($timeout:expr)?] [$($name:ident: $extra:path),*]
+        [$($vpart:literal: [$($vname:ident: $vfunc:path),*]),*]
+    ) => {
+        /// The year of this solution, from its file name.
+        const YEAR: advent_of_code::Year = advent_of_code::Year::__from_solution_path(file!());
+
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        const _: () = assert!(
+            DAY.into_inner() <= YEAR.day_count(),
+            "the day is not part of this year's event"
+        );
+
+        /// The number of parts of this day.
+        const PARTS: u8 = $crate::__solution_parts!(@count [$($parts),*]);
+
+        const _: () = assert!(
+            PARTS == YEAR.event().parts || PARTS == 1,
+            "the number of parts does not match the event, list them with `parts = [...]`"
+        );
+
+        /// The solution of this day, also run in-process by the main binary.
+        pub const SOLUTION: advent_of_code::template::registry::Solution =
+            advent_of_code::template::registry::Solution {
+                year: YEAR,
+                day: DAY,
+                parts: PARTS,
+                files: advent_of_code::template::embedded::files(YEAR, DAY),
+                setup: || {
+                    $(
+                        advent_of_code::template::watchdog::set_time_limit(
+                            advent_of_code::template::watchdog::parse_duration($timeout)
+                                .expect("invalid `timeout` in `solution!`"),
+                        );
+                    )?
+                },
+                solve: |input| {
+                    use advent_of_code::template::runner::*;
+
+                    let parsed = $crate::__solution_parse!(input; [$($parse)?] [$($input)?]);
+                    if let Some(parsed) = parsed {
+                        let arg = || $crate::__solution_arg!(parsed; [$($parse)?] [$($input)?]);
+                        let variants: &[Variant] = &[$($(
+                            Variant {
+                                part: $vpart,
+                                name: stringify!($vname),
+                                run: &|| run_variant($vfunc, arg(), YEAR, DAY, $vpart, stringify!($vname)),
+                            },
+                        )*)*];
+                        check_variants(variants);
+                        $crate::__solution_parts!(arg, variants; [$($parts),*] [$part_one] [$part_two]);
+                        $(run_extra_part($extra, arg(), YEAR, DAY, stringify!($name));)*
+                    }
+                },
+            };
+
         fn main() {
-            use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            advent_of_code::template::runner::run(SOLUTION);
         }
     };
 }
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __solution_parse {
+    ($input:ident; [] []) => {
+        Some($input)
+    };
+    ($input:ident; [$parse:expr] []) => {
+        run_parse($parse, $input, YEAR, DAY).map(|parsed| &*Box::leak(Box::new(parsed)))
+    };
+    ($input:ident; [] [$ty:ty]) => {
+        run_parse(
+            <$ty as advent_of_code::template::solution::FromInput>::from_input,
+            $input,
+            YEAR,
+            DAY,
+        )
+    };
+    ($input:ident; [$parse:expr] [$ty:ty]) => {
+        run_parse(
+            |input| {
+                $parse(<$ty as advent_of_code::template::solution::FromInput>::from_input(input))
+            },
+            $input,
+            YEAR,
+            DAY,
+        )
+        .map(|parsed| &*Box::leak(Box::new(parsed)))
+    };
+}
+
+/// Runs the parts, numbered from 1: those of the `parts` option, or part one and part two.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __solution_parts {
+    (@count []) => {
+        2
+    };
+    (@count [$($part:path),+]) => {
+        [$(stringify!($part)),+].len() as u8
+    };
+    ($arg:ident, $variants:ident; [] [$part_one:expr] [$part_two:expr]) => {
+        run_part_with_variants($part_one, || $arg(), YEAR, DAY, 1, $variants);
+        run_part_with_variants($part_two, || $arg(), YEAR, DAY, 2, $variants);
+    };
+    ($arg:ident, $variants:ident; [$($part:path),+] [$($_:tt)*] [$($__:tt)*]) => {
+        let mut number = 0;
+        $(
+            number += 1;
+            run_part_with_variants($part, || $arg(), YEAR, DAY, number, $variants);
+        )+
+        skip_parts(YEAR, DAY, number);
+    };
+}
+
+/// The input of a part: a copy of the parsed reference or of the input, so that it can coerce
+/// to the part's argument, or a clone of a converted input, which the parts take by value.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __solution_arg {
+    ($parsed:ident; [] [$ty:ty]) => {
+        Clone::clone(&$parsed)
+    };
+    ($parsed:ident; [$($parse:tt)*] [$($input:tt)*]) => {
+        $parsed
+    };
+}
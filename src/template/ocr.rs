@@ -0,0 +1,204 @@
+/// Recognition of answers drawn as ASCII art in the Advent of Code block-letter fonts.
+///
+/// Some puzzles have an answer that is only readable when printed, e.g. a grid that spells out
+/// `EFGHIJKL`. The runner decodes such answers so that they can be submitted and checked as text.
+/// Both fonts are supported: the 4x6 font used by most puzzles and the 6x10 font of 2018 day 10.
+/// The letters may be drawn with `#`/`.` or any other pair of characters.
+use std::{collections::HashMap, sync::OnceLock};
+
+const SMALL_LETTERS: &str = "ABCEFGHIJKLOPRSUYZ";
+const SMALL_FONT: &str = "\
+.##..###...##..####.####..##..#..#.###...##.#..#.#.....##..###..###...###.#..#.#...#.####
+#..#.#..#.#..#.#....#....#..#.#..#..#.....#.#.#..#....#..#.#..#.#..#.#....#..#.#...#....#
+#..#.###..#....###..###..#....####..#.....#.##...#....#..#.#..#.#..#.#....#..#..#.#....#.
+####.#..#.#....#....#....#.##.#..#..#.....#.#.#..#....#..#.###..###...##..#..#...#....#..
+#..#.#..#.#..#.#....#....#..#.#..#..#..#..#.#.#..#....#..#.#....#.#.....#.#..#...#...#...
+#..#.###...##..####.#.....###.#..#.###..##..#..#.####..##..#....#..#.###...##....#...####
+";
+const LARGE_LETTERS: &str = "ABCEFGHJKLNPRXZ";
+const LARGE_FONT: &str = "\
+..##....#####....####...######..######...####...#....#.....###..#....#..#.......#....#..#####...#####...#....#..######
+.#..#...#....#..#....#..#.......#.......#....#..#....#......#...#...#...#.......##...#..#....#..#....#..#....#.......#
+#....#..#....#..#.......#.......#.......#.......#....#......#...#..#....#.......##...#..#....#..#....#...#..#........#
+#....#..#....#..#.......#.......#.......#.......#....#......#...#.#.....#.......#.#..#..#....#..#....#...#..#.......#.
+#....#..#####...#.......#####...#####...#.......######......#...##......#.......#.#..#..#####...#####.....##.......#..
+######..#....#..#.......#.......#.......#..###..#....#......#...##......#.......#..#.#..#.......#..#......##......#...
+#....#..#....#..#.......#.......#.......#....#..#....#......#...#.#.....#.......#..#.#..#.......#...#....#..#....#....
+#....#..#....#..#.......#.......#.......#....#..#....#..#...#...#..#....#.......#...##..#.......#...#....#..#...#.....
+#....#..#....#..#....#..#.......#.......#...##..#....#..#...#...#...#...#.......#...##..#.......#....#..#....#..#.....
+#....#..#####....####...######..#........###.#..#....#...###....#....#..######..#....#..#.......#....#..#....#..######
+";
+
+/// Decodes an answer drawn as ASCII art. Returns [`None`] if the answer is not made up of glyphs
+/// of a known font.
+#[must_use]
+pub fn recognize(art: &str) -> Option<String> {
+    let grid = parse_grid(art)?;
+
+    let font = match grid.len() {
+        6 => font(&SMALL, SMALL_LETTERS, SMALL_FONT),
+        10 => font(&LARGE, LARGE_LETTERS, LARGE_FONT),
+        _ => return None,
+    };
+
+    let glyphs = split_glyphs(&grid);
+    if glyphs.is_empty() {
+        return None;
+    }
+
+    glyphs
+        .iter()
+        .map(|glyph| font.get(glyph).copied())
+        .collect()
+}
+
+static SMALL: OnceLock<HashMap<String, char>> = OnceLock::new();
+static LARGE: OnceLock<HashMap<String, char>> = OnceLock::new();
+
+/// Maps the glyphs of a font to their letters.
+fn font(
+    cache: &'static OnceLock<HashMap<String, char>>,
+    letters: &str,
+    art: &str,
+) -> &'static HashMap<String, char> {
+    cache.get_or_init(|| {
+        let grid = parse_grid(art).expect("font is well formed");
+        let glyphs = split_glyphs(&grid);
+        debug_assert_eq!(glyphs.len(), letters.len());
+        glyphs.into_iter().zip(letters.chars()).collect()
+    })
+}
+
+/// Parses the art into rows of lit cells, with blank rows around it removed.
+fn parse_grid(art: &str) -> Option<Vec<Vec<bool>>> {
+    let lines: Vec<&str> = art.lines().map(|l| l.trim_end_matches('\r')).collect();
+
+    let mut chars: Vec<char> = lines.iter().flat_map(|l| l.chars()).collect();
+    chars.sort_unstable();
+    chars.dedup();
+
+    // lines may have been trimmed if the background is drawn with spaces.
+    let background = match chars.as_slice() {
+        [c] if *c == '.' || *c == ' ' => *c,
+        [a, b] if *a == '#' || *b == '#' => {
+            if *a == '#' {
+                *b
+            } else {
+                *a
+            }
+        }
+        [a, _] if *a == '.' || *a == ' ' => *a,
+        [_, b] if *b == '.' || *b == ' ' => *b,
+        [a, b] => {
+            // otherwise, the letters are drawn with the less frequent character.
+            let count = |c: char| {
+                lines
+                    .iter()
+                    .flat_map(|l| l.chars())
+                    .filter(|x| *x == c)
+                    .count()
+            };
+            if count(*a) > count(*b) {
+                *a
+            } else {
+                *b
+            }
+        }
+        _ => return None,
+    };
+
+    let width = lines.iter().map(|l| l.chars().count()).max()?;
+
+    let mut grid: Vec<Vec<bool>> = lines
+        .iter()
+        .map(|l| {
+            let mut row: Vec<bool> = l.chars().map(|c| c != background).collect();
+            row.resize(width, false);
+            row
+        })
+        .collect();
+
+    while grid.last().is_some_and(|row| !row.contains(&true)) {
+        grid.pop();
+    }
+
+    let first = grid.iter().position(|row| row.contains(&true))?;
+    Some(grid.split_off(first))
+}
+
+/// Splits the grid into glyphs at blank columns. Each glyph is keyed by its rows in `#`/`.`.
+fn split_glyphs(grid: &[Vec<bool>]) -> Vec<String> {
+    let width = grid.first().map_or(0, Vec::len);
+    let is_blank = |x: usize| grid.iter().all(|row| !row[x]);
+
+    let mut glyphs = vec![];
+    let mut start = None;
+
+    for x in 0..=width {
+        match (start, x < width && !is_blank(x)) {
+            (None, true) => start = Some(x),
+            (Some(from), false) => {
+                let glyph: Vec<String> = grid
+                    .iter()
+                    .map(|row| {
+                        row[from..x]
+                            .iter()
+                            .map(|&lit| if lit { '#' } else { '.' })
+                            .collect()
+                    })
+                    .collect();
+                glyphs.push(glyph.join("\n"));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    glyphs
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{recognize, LARGE_FONT, LARGE_LETTERS, SMALL_FONT, SMALL_LETTERS};
+
+    #[test]
+    fn recognizes_small_font() {
+        assert_eq!(recognize(SMALL_FONT).as_deref(), Some(SMALL_LETTERS));
+    }
+
+    #[test]
+    fn recognizes_large_font() {
+        assert_eq!(recognize(LARGE_FONT).as_deref(), Some(LARGE_LETTERS));
+    }
+
+    #[test]
+    fn recognizes_any_characters() {
+        let art = [
+            " ██  ███",
+            "█  █ █  █",
+            "█  █ ███",
+            "████ █  █",
+            "█  █ █  █",
+            "█  █ ███",
+        ]
+        .join("\n");
+        assert_eq!(recognize(&art).as_deref(), Some("AB"));
+
+        let art = SMALL_FONT.replace('#', "X").replace('.', "o");
+        assert_eq!(recognize(&art).as_deref(), Some(SMALL_LETTERS));
+    }
+
+    #[test]
+    fn ignores_surrounding_blank_space() {
+        let art = format!("......\n{}\n......\n", SMALL_FONT.replace('\n', "..\n"));
+        assert_eq!(recognize(&art).as_deref(), Some(SMALL_LETTERS));
+    }
+
+    #[test]
+    fn rejects_unknown_glyphs() {
+        assert_eq!(recognize("#.#\n.#.\n#.#"), None);
+        assert_eq!(recognize("####\n#..#\n#..#\n#..#\n#..#\n####"), None);
+        assert_eq!(recognize("12\n34"), None);
+        assert_eq!(recognize(""), None);
+    }
+}
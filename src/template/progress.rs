@@ -0,0 +1,238 @@
+/// Progress reporting for long-running solutions.
+///
+/// A solution creates a [`Progress`] handle, sets a total and increments it while it works.
+/// The runner draws it as a single updating line on stderr with rate and ETA, but only if stderr
+/// is a terminal. The line is never drawn while benchmarking, while the runner is quiet or when
+/// results are collected as structured records, so progress output never mixes with answers.
+use std::{
+    io::{stderr, IsTerminal, Write},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::template::report::REPORT_FILE_ENV;
+use crate::template::{runner, watchdog};
+
+/// Set by the runner while it benchmarks a part.
+static MUTED: AtomicBool = AtomicBool::new(false);
+
+/// Minimum time between two redraws of the progress line.
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Maximum number of updates between two checks of the clock, to keep `inc` cheap in hot loops.
+const MAX_CLOCK_STRIDE: u64 = 1024;
+
+const BAR_WIDTH: usize = 20;
+
+/// Mutes or unmutes all progress output, e.g. while benchmarking.
+pub fn set_muted(muted: bool) {
+    MUTED.store(muted, Ordering::Relaxed);
+}
+
+fn is_enabled() -> bool {
+    !MUTED.load(Ordering::Relaxed)
+        && !watchdog::is_abandoned()
+        && !runner::is_quiet()
+        && std::env::var_os(REPORT_FILE_ENV).is_none()
+        && stderr().is_terminal()
+}
+
+/// A progress handle for a long-running computation.
+///
+/// ```ignore
+/// let mut progress = Progress::new(seeds.len());
+/// for seed in seeds {
+///     progress.inc(1);
+///     // ...
+/// }
+/// ```
+pub struct Progress {
+    enabled: bool,
+    total: u64,
+    current: u64,
+    message: String,
+    started: Instant,
+    last_draw: Option<Instant>,
+    updates: u64,
+    stride: u64,
+}
+
+impl Progress {
+    /// Creates a progress handle with the given total.
+    #[must_use]
+    pub fn new(total: impl TryInto<u64>) -> Self {
+        let mut progress = Self {
+            enabled: is_enabled(),
+            total: 0,
+            current: 0,
+            message: String::new(),
+            started: Instant::now(),
+            last_draw: None,
+            updates: 0,
+            stride: 1,
+        };
+        progress.set_total(total);
+        progress
+    }
+
+    /// Sets the total, e.g. once it becomes known.
+    pub fn set_total(&mut self, total: impl TryInto<u64>) {
+        self.total = total.try_into().unwrap_or(u64::MAX);
+        // small totals are checked on every update, so that slow steps still get drawn.
+        self.stride = (self.total / 1000).clamp(1, MAX_CLOCK_STRIDE);
+    }
+
+    /// Attaches a message that is shown after the counters.
+    pub fn set_message(&mut self, message: impl Into<String>) {
+        if self.enabled {
+            self.message = message.into();
+            self.tick();
+        }
+    }
+
+    /// Advances the progress by `n`.
+    #[inline]
+    pub fn inc(&mut self, n: u64) {
+        self.current += n;
+
+        if !self.enabled {
+            return;
+        }
+
+        self.updates += 1;
+        if self.updates >= self.stride {
+            self.updates = 0;
+            self.tick();
+        }
+    }
+
+    fn tick(&mut self) {
+        let now = Instant::now();
+        if self
+            .last_draw
+            .is_some_and(|last| now - last < REDRAW_INTERVAL)
+        {
+            return;
+        }
+        self.last_draw = Some(now);
+
+        let line = render(self.current, self.total, now - self.started, &self.message);
+        let mut stderr = stderr();
+        let _ = write!(stderr, "\r\x1b[2K{line}");
+        let _ = stderr.flush();
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if self.enabled && self.last_draw.is_some() {
+            let mut stderr = stderr();
+            let _ = write!(stderr, "\r\x1b[2K");
+            let _ = stderr.flush();
+        }
+    }
+}
+
+/// Renders the progress line, e.g. `[=====>    ] 50% 500/1000 · 1.2k/s · ETA 3s · message`.
+fn render(current: u64, total: u64, elapsed: Duration, message: &str) -> String {
+    #[allow(clippy::cast_precision_loss)]
+    let ratio = if total == 0 {
+        0.0
+    } else {
+        (current as f64 / total as f64).min(1.0)
+    };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let filled = (ratio * BAR_WIDTH as f64) as usize;
+    let bar = match filled {
+        0 => " ".repeat(BAR_WIDTH),
+        n if n >= BAR_WIDTH => "=".repeat(BAR_WIDTH),
+        n => format!("{}>{}", "=".repeat(n - 1), " ".repeat(BAR_WIDTH - n)),
+    };
+
+    #[allow(clippy::cast_precision_loss)]
+    let rate = current as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+
+    #[allow(clippy::cast_precision_loss)]
+    let eta = if current == 0 || current >= total {
+        "-".to_string()
+    } else {
+        format_eta(Duration::from_secs_f64((total - current) as f64 / rate))
+    };
+
+    let mut line = format!(
+        "[{bar}] {:.0}% {current}/{total} · {}/s · ETA {eta}",
+        ratio * 100.0,
+        format_count(rate)
+    );
+
+    if !message.is_empty() {
+        line.push_str(" · ");
+        line.push_str(message);
+    }
+
+    line
+}
+
+fn format_count(value: f64) -> String {
+    match value {
+        v if v >= 1e9 => format!("{:.1}G", v / 1e9),
+        v if v >= 1e6 => format!("{:.1}M", v / 1e6),
+        v if v >= 1e3 => format!("{:.1}k", v / 1e3),
+        v => format!("{v:.0}"),
+    }
+}
+
+fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs();
+    match secs {
+        s if s >= 3600 => format!("{}h{:02}m", s / 3600, (s % 3600) / 60),
+        s if s >= 60 => format!("{}m{:02}s", s / 60, s % 60),
+        s => format!("{s}s"),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_count, format_eta, render};
+    use std::time::Duration;
+
+    #[test]
+    fn renders_progress() {
+        assert_eq!(
+            render(500, 1000, Duration::from_secs(1), ""),
+            "[=========>          ] 50% 500/1000 · 500/s · ETA 1s"
+        );
+    }
+
+    #[test]
+    fn renders_message() {
+        assert_eq!(
+            render(0, 10, Duration::from_secs(1), "seed range 1/10"),
+            "[                    ] 0% 0/10 · 0/s · ETA - · seed range 1/10"
+        );
+    }
+
+    #[test]
+    fn renders_completion() {
+        assert_eq!(
+            render(20, 10, Duration::from_secs(2), ""),
+            "[====================] 100% 20/10 · 10/s · ETA -"
+        );
+    }
+
+    #[test]
+    fn formats_counts() {
+        assert_eq!(format_count(12.0), "12");
+        assert_eq!(format_count(1_500.0), "1.5k");
+        assert_eq!(format_count(2_000_000.0), "2.0M");
+        assert_eq!(format_count(3_100_000_000.0), "3.1G");
+    }
+
+    #[test]
+    fn formats_eta() {
+        assert_eq!(format_eta(Duration::from_secs(42)), "42s");
+        assert_eq!(format_eta(Duration::from_secs(125)), "2m05s");
+        assert_eq!(format_eta(Duration::from_secs(7380)), "2h03m");
+    }
+}
@@ -1,11 +1,19 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
+///
+/// Every year gets its own table between two `<!--- benchmarking table <year> --->` markers.
+/// The table of a new year is inserted above the `<!--- benchmarking table --->` marker.
 use std::{fs, io};
 
-use crate::Day;
+use crate::template::registry;
+use crate::{Day, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+fn year_marker(year: Year) -> String {
+    format!("<!--- benchmarking table {year} --->")
+}
+
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
@@ -21,8 +29,9 @@ impl From<std::io::Error> for Error {
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub parse: Option<String>,
+    /// The timings of the parts, in order.
+    pub parts: Vec<Option<String>>,
     pub total_nanos: f64,
 }
 
@@ -32,99 +41,193 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./src/bin/{}.rs", registry::get_bin_name(year, day))
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Locates the table between two occurences of `marker`, [`None`] if there is none yet.
+fn locate_table(readme: &str, marker: &str) -> Result<Option<TablePosition>, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
     }
 
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
-
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+    let (Some(first), Some(last)) = (matches.first(), matches.last()) else {
+        return Ok(None);
+    };
 
-    Ok(TablePosition { pos_start, pos_end })
+    Ok(Some(TablePosition {
+        pos_start: first.0,
+        pos_end: last.0 + last.1.len(),
+    }))
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(prefix: &str, year: Year, timings: Vec<Timings>, total_millis: f64) -> String {
+    let marker = year_marker(year);
+    let header = format!("{prefix} {year} Benchmarks");
+    let label = year.event().day_label;
+    let parts = year.event().parts;
 
+    let part_columns: String = (1..=parts).map(|part| format!(" Part {part} |")).collect();
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.clone(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| {label} | Parse |{part_columns}"),
+        format!(
+            "| :---: | :---: |{} :---:  |",
+            " :---: |".repeat(usize::from(parts) - 1)
+        ),
     ];
 
     for timing in timings {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        let path = get_path_for_bin(year, timing.day);
+        let format_timing =
+            |timing: Option<String>| format!("`{}`", timing.as_deref().unwrap_or("-"));
+        let mut columns = vec![
+            format!("[{label} {}]({path})", timing.day.into_inner()),
+            format_timing(timing.parse),
+        ];
+        columns.extend(timing.parts.into_iter().map(format_timing));
+        lines.push(format!("| {} |", columns.join(" | ")));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Vec<Timings>,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let table = construct_table("##", year, timings, total_millis);
+
+    if let Some(positions) = locate_table(s, &year_marker(year))? {
+        s.replace_range(positions.pos_start..positions.pos_end, &table);
+        return Ok(());
+    }
+
+    let anchor = s
+        .find(MARKER)
+        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+    s.insert_str(anchor, &format!("{table}\n\n"));
+    Ok(())
+}
+
+/// Parses the rows of a year's table back into timings, e.g.
+/// `| [Day 1](./src/bin/2023-01.rs) | `-` | `10.0ms` | `20.0ms` |`.
+fn parse_table(table: &str, year: Year) -> Vec<Timings> {
+    let label = format!("[{} ", year.event().day_label);
+
+    table
+        .lines()
+        .filter_map(|line| {
+            let cells: Vec<&str> = line
+                .trim()
+                .trim_matches('|')
+                .split('|')
+                .map(str::trim)
+                .collect();
+            let (first, cells) = cells.split_first()?;
+            let day = first
+                .strip_prefix(&label)?
+                .split_once(']')?
+                .0
+                .parse()
+                .ok()?;
+
+            let mut timings = cells.iter().map(|cell| {
+                let timing = cell.trim_matches('`');
+                (timing != "-").then(|| timing.to_string())
+            });
+            let parse = timings.next()?;
+            let parts: Vec<Option<String>> = timings.collect();
+            let total_nanos = parse
+                .iter()
+                .chain(parts.iter().flatten())
+                .filter_map(|t| parse_nanos(t))
+                .sum();
+
+            Some(Timings {
+                day,
+                parse,
+                parts,
+                total_nanos,
+            })
+        })
+        .collect()
+}
+
+/// Parses a formatted duration like `74.1µs` into nanoseconds. [`None`] for `timed out` and `n/a`.
+fn parse_nanos(timing: &str) -> Option<f64> {
+    [("ns", 1e0), ("µs", 1e3), ("ms", 1e6), ("s", 1e9)]
+        .into_iter()
+        .find_map(|(unit, nanos)| Some(timing.strip_suffix(unit)?.parse::<f64>().ok()? * nanos))
+}
+
+/// Replaces the timings of the given days in a year's table, keeping the other days.
+fn merge_content(s: &mut String, year: Year, timings: Vec<Timings>) -> Result<(), Error> {
+    let mut merged = match locate_table(s, &year_marker(year))? {
+        Some(positions) => parse_table(&s[positions.pos_start..positions.pos_end], year),
+        None => vec![],
+    };
+
+    merged.retain(|existing| timings.iter().all(|timing| timing.day != existing.day));
+    merged.extend(timings);
+    merged.sort_by_key(|timing| timing.day);
+
+    let total_millis = merged.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
+    update_content(s, year, merged, total_millis)
+}
+
+pub fn update(year: Year, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, year, timings, total_millis)?;
+    fs::write(path, &readme)?;
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+/// Like [`update`], but only replaces the timings of the given days in the year's table.
+pub fn merge(year: Year, timings: Vec<Timings>) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    merge_content(&mut readme, year, timings)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use super::{merge_content, update_content, year_marker, Timings, MARKER};
+    use crate::{day, year};
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
                 day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
+                parse: None,
+                parts: vec![Some("10ms".into()), Some("20ms".into())],
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
+                parse: Some("5ms".into()),
+                parts: vec![Some("30ms".into()), Some("40ms".into())],
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
+                parse: None,
+                parts: vec![Some("40ms".into()), Some("50ms".into())],
                 total_nanos: 9e+10,
             },
         ]
@@ -134,53 +237,131 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
-        let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let marker = year_marker(year!(2023));
+        let mut s = format!("{} {} {}", marker, marker, marker);
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        let mut s = format!("foo\nbar\n{}\nbaz", MARKER);
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.contains("## 2023 Benchmarks"), true);
+        assert_eq!(s.contains(MARKER), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        let mut s = format!("foo\nbar\n{}\nbaz", MARKER);
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+        let marker = year_marker(year!(2023));
+        assert_eq!(s.matches(&marker).collect::<Vec<&str>>().len(), 2);
+        assert_eq!(
+            s.matches("## 2023 Benchmarks").collect::<Vec<&str>>().len(),
+            1
+        );
+    }
+
+    #[test]
+    fn adds_a_table_per_year() {
+        let mut s = format!("foo\n{}\nbaz", MARKER);
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2024), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+        let pos_2023 = s.find("## 2023 Benchmarks").unwrap();
+        let pos_2024 = s.find("## 2024 Benchmarks").unwrap();
+        assert!(pos_2023 < pos_2024 && pos_2024 < s.find(MARKER).unwrap());
+        assert_eq!(s.matches("Benchmarks").count(), 2);
+    }
+
+    #[test]
+    fn formats_other_events() {
+        let year = "ec2024".parse().unwrap();
+        let timings = vec![Timings {
+            day: day!(3),
+            parse: None,
+            parts: vec![Some("1ms".into()), Some("2ms".into()), None],
+            total_nanos: 3e+6,
+        }];
+        let mut s = MARKER.to_string();
+        update_content(&mut s, year, timings, 3.0).unwrap();
+        assert!(s.contains("## ec2024 Benchmarks"));
+        assert!(s.contains("| Quest | Parse | Part 1 | Part 2 | Part 3 |\n"));
+        assert!(s.contains("| :---: | :---: | :---: | :---: | :---:  |\n"));
+        assert!(s.contains("| [Quest 3](./src/bin/ec2024-03.rs) | `-` | `1ms` | `2ms` | `-` |"));
     }
 
     #[test]
     fn format_benchmarks() {
-        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let mut s = format!("foo\nbar\n{}\nbaz", MARKER);
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
-            "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "<!--- benchmarking table 2023 --->",
+            "## 2023 Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2023-01.rs) | `-` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `5ms` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2023-04.rs) | `-` | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
+            "<!--- benchmarking table 2023 --->",
+            "",
             "<!--- benchmarking table --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn merges_benchmarks_of_some_days() {
+        let mut s = format!("foo\n{}\nbaz", MARKER);
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+        let timings = vec![
+            Timings {
+                day: day!(3),
+                parse: None,
+                parts: vec![Some("1.5ms".into()), Some("timed out".into())],
+                total_nanos: 1.5e+6,
+            },
+            Timings {
+                day: day!(2),
+                parse: None,
+                parts: vec![Some("250.0µs".into()), Some("1.0s".into())],
+                total_nanos: 1.00025e+9,
+            },
+        ];
+        merge_content(&mut s, year!(2023), timings).unwrap();
+
+        let expected = [
+            "| [Day 1](./src/bin/2023-01.rs) | `-` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `-` | `250.0µs` | `1.0s` |",
+            "| [Day 3](./src/bin/2023-03.rs) | `-` | `1.5ms` | `timed out` |",
+            "| [Day 4](./src/bin/2023-04.rs) | `-` | `40ms` | `50ms` |",
+            "",
+            "**Total: 1121.75ms**",
+        ]
+        .join("\n");
+        assert!(s.contains(&expected), "{s}");
+        assert_eq!(s.matches("## 2023 Benchmarks").count(), 1);
+    }
+
+    #[test]
+    fn merges_into_a_new_table() {
+        let mut s = MARKER.to_string();
+        merge_content(&mut s, year!(2023), get_mock_timings()[..1].to_vec()).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/2023-01.rs) | `-` | `10ms` | `20ms` |"));
+        assert!(s.contains("**Total: 30000.00ms**"));
+    }
 }
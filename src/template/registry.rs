@@ -0,0 +1,54 @@
+/// The solutions compiled into the main binary.
+///
+/// Besides its own binary, every day's [`solution!`](crate::solution) is compiled into the main
+/// binary by `build.rs`, which lists the [`Solution`] of each `src/bin/<year>-<day>.rs`. The
+/// `solve` and `all` commands use this to run solutions in-process instead of spawning
+/// `cargo run` per day.
+use crate::template::embedded::File;
+use crate::{Day, DaySet, Year};
+
+/// A day's solution, as declared by the [`solution!`](crate::solution) macro.
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    pub year: Year,
+    pub day: Day,
+    /// Applies the options of the `solution!` macro that configure the runner, e.g. `timeout`.
+    pub setup: fn(),
+    /// The number of parts: those of its event, or `1` for a single-part day.
+    pub parts: u8,
+    /// Runs the parse stage, if any, and the parts on an input.
+    pub solve: fn(&'static str),
+    /// The inputs and examples embedded with the `embed_inputs` feature, see
+    /// [`embedded`](crate::template::embedded).
+    pub files: &'static [File],
+}
+
+/// Finds the solution of a day.
+#[must_use]
+pub fn find(solutions: &[Solution], year: Year, day: Day) -> Option<Solution> {
+    solutions
+        .iter()
+        .find(|solution| solution.year == year && solution.day == day)
+        .copied()
+}
+
+/// The years that have solutions, in ascending order.
+#[must_use]
+pub fn years(solutions: &[Solution]) -> Vec<Year> {
+    let mut years: Vec<Year> = solutions.iter().map(|solution| solution.year).collect();
+    years.sort();
+    years.dedup();
+    years
+}
+
+/// The days of a year in a [`DaySet`], where `solved` selects the days that have a solution.
+#[must_use]
+pub fn select_days(solutions: &[Solution], year: Year, days: &DaySet) -> Vec<Day> {
+    days.days(year, |day| find(solutions, year, day).is_some())
+}
+
+/// The name of a day's solution binary, e.g. `2023-08`.
+#[must_use]
+pub fn get_bin_name(year: Year, day: Day) -> String {
+    format!("{year}-{day}")
+}
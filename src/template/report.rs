@@ -0,0 +1,453 @@
+/// Machine-readable results of solution runs.
+///
+/// If the `AOC_REPORT_FILE` environment variable is set, the runner appends one JSON object per
+/// line to that file for every stage it runs. The `all` command uses this to collect answers and
+/// timings from the solution binaries without parsing their human-readable output.
+use std::{
+    collections::HashMap,
+    env,
+    fmt::{Display, Write as _},
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+    sync::Mutex,
+};
+
+use crate::template::answers::Check;
+use crate::{Day, Year};
+
+/// Name of the environment variable that selects the report file.
+pub const REPORT_FILE_ENV: &str = "AOC_REPORT_FILE";
+
+/// The records emitted while [`collect`]ing.
+static COLLECTED: Mutex<Option<Vec<Record>>> = Mutex::new(None);
+
+/// The outcome of a stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    Unsolved,
+    /// The stage did not finish within the time limit.
+    TimedOut,
+    /// The part returned an error, see the record's message.
+    Failed,
+    /// The stage panicked, see the record's message.
+    Panicked,
+    /// The part does not exist for a single-part day, e.g. part two of the last day.
+    NotApplicable,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::TimedOut => "timed_out",
+            Status::Failed => "failed",
+            Status::Panicked => "panicked",
+            Status::NotApplicable => "n/a",
+        })
+    }
+}
+
+impl FromStr for Status {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Status::Solved),
+            "unsolved" => Ok(Status::Unsolved),
+            "timed_out" => Ok(Status::TimedOut),
+            "failed" => Ok(Status::Failed),
+            "panicked" => Ok(Status::Panicked),
+            "n/a" => Ok(Status::NotApplicable),
+            _ => Err(Error::Parser(format!("unknown status `{s}`"))),
+        }
+    }
+}
+
+/// The result of running one stage (the parse stage or a part) of a solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub year: Year,
+    pub day: Day,
+    /// `parse` for the parse stage, otherwise the part number.
+    pub part: String,
+    pub status: Status,
+    pub answer: Option<String>,
+    /// How the answer compares to the accepted answer of the part.
+    pub check: Check,
+    /// Details on why the stage failed, e.g. the panic message or the expected answer.
+    pub message: Option<String>,
+    /// The median execution time in nanoseconds.
+    pub nanos: u128,
+    pub samples: u128,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(msg) => write!(f, "malformed report: {msg}"),
+            Error::IO(e) => write!(f, "could not access report file: {e}"),
+        }
+    }
+}
+
+impl Record {
+    /// Serializes the record as a single line of JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let optional =
+            |value: &Option<String>| value.as_deref().map_or_else(|| "null".into(), json_string);
+
+        format!(
+            r#"{{"year":{},"day":{},"part":{},"status":{},"answer":{},"check":{},"message":{},"nanos":{},"samples":{}}}"#,
+            json_string(&self.year.to_string()),
+            self.day.into_inner(),
+            json_string(&self.part),
+            json_string(&self.status.to_string()),
+            optional(&self.answer),
+            json_string(&self.check.to_string()),
+            optional(&self.message),
+            self.nanos,
+            self.samples
+        )
+    }
+
+    /// Parses a record from a line of JSON written by [`Record::to_json`].
+    pub fn from_json(line: &str) -> Result<Self, Error> {
+        let mut fields = parse_object(line)?;
+
+        let mut take = |key: &str| {
+            fields
+                .remove(key)
+                .ok_or_else(|| Error::Parser(format!("missing field `{key}`")))
+        };
+
+        let year = take("year")?
+            .into_string()?
+            .parse::<Year>()
+            .map_err(|e| Error::Parser(e.to_string()))?;
+
+        let day = take("day")?
+            .into_number()?
+            .to_string()
+            .parse::<Day>()
+            .map_err(|e| Error::Parser(e.to_string()))?;
+
+        Ok(Record {
+            year,
+            day,
+            part: take("part")?.into_string()?,
+            status: take("status")?.into_string()?.parse()?,
+            answer: take("answer")?.into_optional_string()?,
+            check: take("check")?
+                .into_string()?
+                .parse()
+                .map_err(Error::Parser)?,
+            message: take("message")?.into_optional_string()?,
+            nanos: take("nanos")?.into_number()?,
+            samples: take("samples")?.into_number()?,
+        })
+    }
+}
+
+/// Appends a record to the report file, if one was requested via [`REPORT_FILE_ENV`], and to the
+/// records being [`collect`]ed.
+pub fn emit(record: &Record) {
+    if let Some(records) = COLLECTED.lock().unwrap().as_mut() {
+        records.push(record.clone());
+    }
+
+    let Ok(path) = env::var(REPORT_FILE_ENV) else {
+        return;
+    };
+
+    let result = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| writeln!(file, "{}", record.to_json()));
+
+    if let Err(e) = result {
+        eprintln!("Failed to write report to \"{path}\": {e}");
+    }
+}
+
+/// Runs `func` and collects the records it emits, for solutions that run in this process.
+pub fn collect<T>(func: impl FnOnce() -> T) -> (T, Vec<Record>) {
+    *COLLECTED.lock().unwrap() = Some(vec![]);
+    let result = func();
+    let records = COLLECTED.lock().unwrap().take().unwrap_or_default();
+    (result, records)
+}
+
+/// Reads all records from a report file. A missing file yields no records.
+pub fn read(path: &Path) -> Result<Vec<Record>, Error> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    fs::read_to_string(path)?
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(Record::from_json)
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The subset of JSON values used by records.
+#[derive(Debug, PartialEq, Eq)]
+enum Value {
+    Null,
+    String(String),
+    Number(u128),
+}
+
+impl Value {
+    fn into_string(self) -> Result<String, Error> {
+        match self {
+            Value::String(s) => Ok(s),
+            v => Err(Error::Parser(format!("expected a string, got {v:?}"))),
+        }
+    }
+
+    fn into_optional_string(self) -> Result<Option<String>, Error> {
+        match self {
+            Value::Null => Ok(None),
+            v => v.into_string().map(Some),
+        }
+    }
+
+    fn into_number(self) -> Result<u128, Error> {
+        match self {
+            Value::Number(n) => Ok(n),
+            v => Err(Error::Parser(format!("expected a number, got {v:?}"))),
+        }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Parses a flat JSON object whose values are strings, unsigned integers or `null`.
+fn parse_object(s: &str) -> Result<HashMap<String, Value>, Error> {
+    let mut chars = s.trim().chars().peekable();
+    let mut fields = HashMap::new();
+
+    let unexpected = |c: Option<char>| Error::Parser(format!("unexpected {c:?}"));
+
+    if chars.next() != Some('{') {
+        return Err(Error::Parser("expected an object".into()));
+    }
+
+    loop {
+        skip_whitespace(&mut chars);
+        match chars.next() {
+            Some('}') if fields.is_empty() => break,
+            Some('"') => {}
+            c => return Err(unexpected(c)),
+        }
+
+        let key = parse_string(&mut chars)?;
+
+        skip_whitespace(&mut chars);
+        if chars.next() != Some(':') {
+            return Err(Error::Parser(format!("expected `:` after `{key}`")));
+        }
+        skip_whitespace(&mut chars);
+
+        let value = match chars.peek() {
+            Some('"') => {
+                chars.next();
+                Value::String(parse_string(&mut chars)?)
+            }
+            Some('n') => {
+                let literal: String = chars.by_ref().take(4).collect();
+                if literal != "null" {
+                    return Err(Error::Parser(format!("unexpected literal `{literal}`")));
+                }
+                Value::Null
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let mut digits = String::new();
+                while let Some(c) = chars.next_if(char::is_ascii_digit) {
+                    digits.push(c);
+                }
+                Value::Number(digits.parse().map_err(|_| unexpected(None))?)
+            }
+            c => return Err(unexpected(c.copied())),
+        };
+
+        fields.insert(key, value);
+
+        skip_whitespace(&mut chars);
+        match chars.next() {
+            Some(',') => {}
+            Some('}') => break,
+            c => return Err(unexpected(c)),
+        }
+    }
+
+    Ok(fields)
+}
+
+fn skip_whitespace(chars: &mut std::iter::Peekable<std::str::Chars>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+/// Parses the remainder of a string whose opening quote has already been consumed.
+fn parse_string(chars: &mut std::iter::Peekable<std::str::Chars>) -> Result<String, Error> {
+    let mut out = String::new();
+    loop {
+        match chars.next() {
+            None => return Err(Error::Parser("unterminated string".into())),
+            Some('"') => return Ok(out),
+            Some('\\') => match chars.next() {
+                Some('"') => out.push('"'),
+                Some('\\') => out.push('\\'),
+                Some('/') => out.push('/'),
+                Some('n') => out.push('\n'),
+                Some('r') => out.push('\r'),
+                Some('t') => out.push('\t'),
+                Some('u') => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    let c = u32::from_str_radix(&hex, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| Error::Parser(format!("invalid escape `\\u{hex}`")))?;
+                    out.push(c);
+                }
+                c => return Err(Error::Parser(format!("invalid escape {c:?}"))),
+            },
+            Some(c) => out.push(c),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_object, Record, Status, Value};
+    use crate::template::answers::Check;
+    use crate::{day, year};
+
+    fn record() -> Record {
+        Record {
+            year: year!(2023),
+            day: day!(8),
+            part: "1".into(),
+            status: Status::Solved,
+            answer: Some("42".into()),
+            check: Check::Match,
+            message: None,
+            nanos: 74130,
+            samples: 100,
+        }
+    }
+
+    #[test]
+    fn serializes_record() {
+        assert_eq!(
+            record().to_json(),
+            r#"{"year":"2023","day":8,"part":"1","status":"solved","answer":"42","check":"match","message":null,"nanos":74130,"samples":100}"#
+        );
+    }
+
+    #[test]
+    fn round_trips_record() {
+        let record = record();
+        assert_eq!(Record::from_json(&record.to_json()).unwrap(), record);
+    }
+
+    #[test]
+    fn round_trips_special_characters() {
+        let record = Record {
+            answer: Some("#..#\n\"quoted\" \\ \t\u{1b}".into()),
+            ..record()
+        };
+        assert_eq!(Record::from_json(&record.to_json()).unwrap(), record);
+    }
+
+    #[test]
+    fn round_trips_unsolved() {
+        let record = Record {
+            status: Status::Unsolved,
+            answer: None,
+            check: Check::Unknown,
+            ..record()
+        };
+        assert!(record.to_json().contains(r#""answer":null"#));
+        assert_eq!(Record::from_json(&record.to_json()).unwrap(), record);
+    }
+
+    #[test]
+    fn round_trips_panicked() {
+        let record = Record {
+            status: Status::Panicked,
+            answer: None,
+            check: Check::Unknown,
+            message: Some("panicked at src/bin/08.rs:77:17: not found: XYZ".into()),
+            ..record()
+        };
+        assert_eq!(Record::from_json(&record.to_json()).unwrap(), record);
+    }
+
+    #[test]
+    fn round_trips_mismatch() {
+        let record = Record {
+            answer: Some("43".into()),
+            check: Check::Mismatch,
+            message: Some("expected 42".into()),
+            ..record()
+        };
+        assert!(record.to_json().contains(r#""check":"mismatch""#));
+        assert_eq!(Record::from_json(&record.to_json()).unwrap(), record);
+    }
+
+    #[test]
+    fn parses_whitespace() {
+        let fields = parse_object(r#" { "a" : "b" , "c" : 1 , "d" : null } "#).unwrap();
+        assert_eq!(fields["a"], Value::String("b".into()));
+        assert_eq!(fields["c"], Value::Number(1));
+        assert_eq!(fields["d"], Value::Null);
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert!(Record::from_json("Part 1: 42 (1.0ms)").is_err());
+        assert!(Record::from_json(r#"{"year":"2023","day":8,"part":"1""#).is_err());
+        assert!(Record::from_json(r#"{"day":8}"#).is_err());
+        assert!(Record::from_json(&record().to_json().replace("solved", "great")).is_err());
+    }
+}
@@ -1,66 +1,741 @@
 /// Encapsulates code that interacts with solution functions.
+use crate::template::alloc::{self, AllocStats};
+use crate::template::answers::{Answers, Check};
+use crate::template::bench::BenchConfig;
+use crate::template::embedded;
+use crate::template::examples::{get_examples_path, read_examples};
+use crate::template::input::{self, Input};
+use crate::template::ocr;
+use crate::template::progress;
+use crate::template::registry::Solution;
+use crate::template::report::{self, Record, Status, REPORT_FILE_ENV};
+use crate::template::solution::{Answer, PartResult};
+use crate::template::stats::Stats;
+use crate::template::trace;
+use crate::template::unwind::{self, Panic, EXIT_PANICKED};
+use crate::template::visualize;
+use crate::template::watchdog;
 use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::{Day, Year};
 use std::fmt::Display;
 use std::io::{stdout, Write};
 use std::process::Output;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Whether the text output is muted, see [`set_quiet`].
+static QUIET: AtomicBool = AtomicBool::new(false);
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+/// Prints to stdout unless the text output is muted.
+macro_rules! out {
+    ($($arg:tt)*) => {
+        if !QUIET.load(Ordering::Relaxed) {
+            print!($($arg)*);
+        }
+    };
+}
+
+/// Prints a line to stdout unless the text output is muted.
+macro_rules! outln {
+    ($($arg:tt)*) => {
+        if !QUIET.load(Ordering::Relaxed) {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Whether any stage of this run panicked, see [`run_solution`].
+static PANICKED: AtomicBool = AtomicBool::new(false);
+
+/// Whether any stage of this process was benchmarked, see [`finish`].
+static BENCHED: AtomicBool = AtomicBool::new(false);
+
+/// The answers that results are checked against, see [`answers`].
+static EXPECTED: Mutex<Option<Answers>> = Mutex::new(None);
+
+/// The checked parts while running examples, [`None`] for a regular run.
+static EXAMPLES: Mutex<Option<ExampleTally>> = Mutex::new(None);
+
+/// The runs of a part and its variants while they are compared with `--variants`, see
+/// [`run_part_with_variants`].
+static VARIANT_ROWS: Mutex<Option<Vec<VariantRow>>> = Mutex::new(None);
+
+/// Whether any variant returned a different answer than its part in this run.
+static VARIANTS_DIFFER: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Default)]
+struct ExampleTally {
+    passed: usize,
+    failed: usize,
+}
+
+/// A named alternative implementation of a part, declared with the `variants` option of
+/// [`solution!`](crate::solution).
+pub struct Variant<'a> {
+    pub part: u8,
+    pub name: &'static str,
+    /// Runs the variant on the input with [`run_variant`].
+    pub run: &'a dyn Fn(),
+}
+
+/// Which implementations of the parts run, from the arguments.
+#[derive(Debug, PartialEq, Eq)]
+enum VariantSelection {
+    /// The parts themselves.
+    Default,
+    /// The variant with this name, where a part has one: `--variant <name>`.
+    One(String),
+    /// The parts and all of their variants: `--variants`.
+    All,
+}
+
+/// The result of a part or one of its variants while comparing them.
+#[derive(Debug)]
+struct VariantRow {
+    /// `default` for the part itself.
+    name: String,
+    answer: Option<String>,
+    status: Status,
+    /// [`None`] if the run did not finish.
+    median: Option<Duration>,
+}
+
+/// The outcome of running a stage under the time limit with panics caught.
+enum Outcome<T> {
+    Finished(T, Stats, Option<AllocStats>),
+    TimedOut(Duration),
+    Panicked(Panic),
+}
+
+/// Runs a solution binary: runs the solution in this process, then [`finish`]es. The binary also
+/// fails if a variant returned a different answer than its part.
+pub fn run(solution: Solution) {
+    let mut exit_code = run_solution(&solution);
+
+    // `all` counts a differing variant as a wrong answer instead of a failed day.
+    if exit_code == 0
+        && VARIANTS_DIFFER.load(Ordering::Relaxed)
+        && env::var_os(REPORT_FILE_ENV).is_none()
+    {
+        exit_code = 1;
+    }
+
+    finish(exit_code);
+}
+
+/// Runs a solution on its input, or on each of its examples with `--examples`. Returns the exit
+/// code of the run: [`EXIT_PANICKED`] if any stage panicked, `1` if the input could not be read
+/// or any example failed, `0` otherwise.
+///
+/// The state of a previous run is reset, so that the main binary can run several days.
+pub fn run_solution(solution: &Solution) -> i32 {
+    let (year, day) = (solution.year, solution.day);
+
+    if let Some(Err(message)) =
+        requested_submit_part().map(|part| check_submit_part(year, day, solution.parts, part))
+    {
+        eprintln!("{message}");
+        return 1;
+    }
+
+    PANICKED.store(false, Ordering::Relaxed);
+    VARIANTS_DIFFER.store(false, Ordering::Relaxed);
+    *EXPECTED.lock().unwrap() = None;
+    *EXAMPLES.lock().unwrap() = None;
+    watchdog::reset_time_limit();
+    embedded::select(year, day, solution.files);
+
+    (solution.setup)();
+    trace::init(year, day);
+    visualize::init();
+
+    let exit_code = if input::selected() == Some(Input::Examples) {
+        run_examples(year, day, solution.solve)
+    } else {
+        match input::try_load(year, day) {
+            // the input lives until the process exits, so that parts can run on their own thread.
+            Ok(input) => {
+                (solution.solve)(Box::leak(input.into_boxed_str()));
+                0
+            }
+            Err(message) => {
+                eprintln!("{message}");
+                1
+            }
+        }
+    };
+
+    visualize::output(year, day);
+
+    let examples_failed = EXAMPLES
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|tally| tally.failed > 0);
+
+    if PANICKED.load(Ordering::Relaxed) {
+        EXIT_PANICKED
+    } else if examples_failed {
+        1
+    } else {
+        exit_code
+    }
+}
+
+/// Runs a solution on an input with all output muted and returns the records of its stages,
+/// e.g. to find inputs that make it panic. Each stage is limited to `limit`, regardless of the
+/// `timeout` of the solution, and results are not checked against the answers.
+///
+/// Like that of a regular run, the input lives until the process exits, as do stages that time
+/// out. The `fuzz` command therefore calls this once per child process.
+pub fn run_on_input(solution: &Solution, input: &str, limit: Duration) -> Vec<Record> {
+    PANICKED.store(false, Ordering::Relaxed);
+    *EXPECTED.lock().unwrap() = Some(Answers::default());
+    *EXAMPLES.lock().unwrap() = None;
+
+    (solution.setup)();
+    watchdog::set_time_limit(limit);
+
+    let _muted = OutputMute::new();
+    let quiet = QUIET.swap(true, Ordering::Relaxed);
+    let ((), records) = report::collect(|| (solution.solve)(Box::leak(input.into())));
+    QUIET.store(quiet, Ordering::Relaxed);
+
+    records
+}
+
+/// Runs a solution on each of its examples. Returns `1` if it has none.
+fn run_examples(year: Year, day: Day, solve: fn(&'static str)) -> i32 {
+    let examples = match read_examples(year, day) {
+        Ok(examples) => examples,
+        Err(e) => {
+            eprintln!("Could not read the examples of day {day}: {e}");
+            return 1;
+        }
+    };
+
+    if examples.is_empty() {
+        eprintln!(
+            "No examples found in \"{}\".",
+            get_examples_path(year, day).display()
+        );
+        return 1;
+    }
+
+    *EXAMPLES.lock().unwrap() = Some(ExampleTally::default());
+
+    for (ix, example) in examples.into_iter().enumerate() {
+        if ix > 0 {
+            outln!();
+        }
+
+        outln!("{ANSI_BOLD}Example {}{ANSI_RESET}", example.name);
+        *EXPECTED.lock().unwrap() = Some(example.answers);
+        solve(Box::leak(example.input.into_boxed_str()));
+    }
+
+    if let Some(tally) = EXAMPLES.lock().unwrap().as_ref() {
+        outln!(
+            "\n{ANSI_BOLD}Examples:{ANSI_RESET} {} passed · {} failed",
+            tally.passed,
+            tally.failed
+        );
+    }
+
+    0
+}
+
+/// Runs the parse stage of a solution, timing it separately from the parts.
+/// Returns [`None`] if parsing timed out or panicked.
+pub fn run_parse<I, P>(func: fn(I) -> P, input: I, year: Year, day: Day) -> Option<P>
+where
+    I: Clone + Send + 'static,
+    P: Send + 'static,
+{
+    let (parsed, stats, alloc) = match run_isolated(func, input, |_: &P| out!("Parse:")) {
+        Outcome::Finished(parsed, stats, alloc) => (parsed, stats, alloc),
+        outcome => {
+            let _ = report_failure(&outcome, "Parse", year, day, "parse");
+            return None;
+        }
+    };
+
+    out!("\r");
+    outln!(
+        "Parse:{}{}             ",
+        format_duration(&stats),
+        format_alloc(alloc)
+    );
+    print_stats(&stats);
+
+    report::emit(&Record {
+        year,
+        day,
+        part: "parse".into(),
+        status: Status::Solved,
+        answer: None,
+        check: Check::Unknown,
+        message: None,
+        nanos: stats.median.as_nanos(),
+        samples: stats.samples,
+    });
+
+    Some(parsed)
+}
+
+/// Runs a part of a solution. A panic inside the part is caught and reported in place of its
+/// result, so that the other part still runs.
+pub fn run_part<I, R>(func: fn(I) -> R, input: I, year: Year, day: Day, part: u8)
+where
+    I: Clone + Send + 'static,
+    R: PartResult + Send + 'static,
+{
+    let id = PartId {
+        label: format!("Part {part}"),
+        answer: part.to_string(),
+        record: part.to_string(),
+        submit: Some(part),
+        variant: None,
+    };
+    run_named_part(func, input, year, day, &id);
+}
+
+/// Runs a part, or the variant of it selected with `--variant`. With `--variants`, each variant of
+/// the part runs after it and is checked against its answer, and their timings are compared in a
+/// table.
+pub fn run_part_with_variants<I, R>(
+    func: fn(I) -> R,
+    input: impl Fn() -> I,
+    year: Year,
+    day: Day,
+    part: u8,
+    variants: &[Variant],
+) where
+    I: Clone + Send + 'static,
+    R: PartResult + Send + 'static,
+{
+    let variants: Vec<&Variant> = variants.iter().filter(|v| v.part == part).collect();
+
+    match variant_selection() {
+        VariantSelection::One(name) => match variants.iter().find(|v| v.name == name) {
+            Some(variant) => (variant.run)(),
+            None => run_part(func, input(), year, day, part),
+        },
+        VariantSelection::All if !variants.is_empty() => {
+            *VARIANT_ROWS.lock().unwrap() = Some(vec![]);
+
+            run_part(func, input(), year, day, part);
+            for variant in variants {
+                (variant.run)();
+            }
+
+            let rows = VARIANT_ROWS.lock().unwrap().take().unwrap_or_default();
+            print_variants(part, &rows);
+        }
+        _ => run_part(func, input(), year, day, part),
+    }
+}
+
+/// Runs a variant of a part, see [`run_part_with_variants`]. A selected variant takes the place of
+/// its part: its answer is checked, reported and submitted as the part's. While comparing
+/// variants, it is reported as `<part>:<name>` and checked against the answer of the part as well.
+pub fn run_variant<I, R>(func: fn(I) -> R, input: I, year: Year, day: Day, part: u8, name: &str)
+where
+    I: Clone + Send + 'static,
+    R: PartResult + Send + 'static,
+{
+    let comparing = VARIANT_ROWS.lock().unwrap().is_some();
+
+    let id = PartId {
+        label: format!("Part {part} ({name})"),
+        answer: part.to_string(),
+        record: if comparing {
+            format!("{part}:{name}")
+        } else {
+            part.to_string()
+        },
+        submit: (!comparing).then_some(part),
+        variant: Some(name),
+    };
+    run_named_part(func, input, year, day, &id);
+}
+
+/// Warns if a day has variants, but not the one selected with `--variant`. Days without variants
+/// run their parts silently, so that `all --variant <name>` only mentions the days it concerns.
+pub fn check_variants(variants: &[Variant]) {
+    let VariantSelection::One(name) = variant_selection() else {
+        return;
+    };
+
+    if !variants.is_empty() && !variants.iter().any(|v| v.name == name) {
+        let mut names: Vec<&str> = variants.iter().map(|v| v.name).collect();
+        names.sort_unstable();
+        names.dedup();
+        eprintln!(
+            "Ignoring --variant {name}: expecting one of {}.",
+            names.join(", ")
+        );
+    }
+}
+
+/// Shows the parts after `last` that a single-part day doesn't have as `n/a`.
+pub fn skip_parts(year: Year, day: Day, last: u8) {
+    for part in last + 1..=year.event().parts {
+        outln!("Part {part}: {ANSI_ITALIC}n/a{ANSI_RESET}");
+
+        report::emit(&Record {
+            year,
+            day,
+            part: part.to_string(),
+            status: Status::NotApplicable,
+            answer: None,
+            check: Check::Unknown,
+            message: None,
+            nanos: 0,
+            samples: 0,
+        });
+    }
+}
+
+/// Runs an extra part of a solution, declared with the `extra` option of
+/// [`solution!`](crate::solution). It is shown, reported and checked under its name, and never
+/// submitted.
+pub fn run_extra_part<I, R>(func: fn(I) -> R, input: I, year: Year, day: Day, name: &str)
+where
+    I: Clone + Send + 'static,
+    R: PartResult + Send + 'static,
+{
+    let id = PartId {
+        label: name.to_string(),
+        answer: name.to_string(),
+        record: name.to_string(),
+        submit: None,
+        variant: None,
+    };
+    run_named_part(func, input, year, day, &id);
+}
+
+/// How a part is identified in the output, the answers and reports.
+struct PartId<'a> {
+    /// Shown in front of the result, e.g. `Part 2 (fast)`.
+    label: String,
+    /// The id of the part in the answers, e.g. `2`.
+    answer: String,
+    /// The part of its record, e.g. `2:fast` for a variant that is compared to its part.
+    record: String,
+    /// The part that the answer is submitted as, if any.
+    submit: Option<u8>,
+    /// The name of the variant, if it is one.
+    variant: Option<&'a str>,
+}
+
+/// Runs a part that is identified by `id`.
+fn run_named_part<I, R>(func: fn(I) -> R, input: I, year: Year, day: Day, id: &PartId)
+where
+    I: Clone + Send + 'static,
+    R: PartResult + Send + 'static,
+{
+    let part_str = id.label.clone();
+
+    if is_running_examples() && answers(year, day).get(&id.answer).is_none() {
+        outln!("{part_str}: {ANSI_ITALIC}skipped, no expected answer{ANSI_RESET}");
+        return;
+    }
+
+    let hook_part_str = part_str.clone();
+    let hook = move |result: &R| print_result(&result.to_answer(), &hook_part_str, "");
+
+    let (result, stats, alloc) = match run_isolated(func, input, hook) {
+        Outcome::Finished(result, stats, alloc) => (result, stats, alloc),
+        outcome => {
+            let status = report_failure(&outcome, &part_str, year, day, &id.record);
+            add_variant_row(id, None, status, None);
+            return;
+        }
+    };
+
+    // art answers are checked, reported and submitted as their decoded letters.
+    let answer = match result.to_answer() {
+        Answer::Solved(art) if art.contains('\n') => {
+            Answer::Solved(ocr::recognize(&art).unwrap_or(art))
+        }
+        answer => answer,
+    };
+    let accepted = answers(year, day);
+    let mut expected = accepted.get(&id.answer).map(ToString::to_string);
+    let mut check = answer
+        .solved()
+        .map_or(Check::Unknown, |answer| accepted.check(&id.answer, answer));
+
+    // a variant that is compared to its part must return the same answer.
+    if let (Some(_), Some(baseline)) = (id.variant, variant_baseline()) {
+        if answer.solved() != Some(baseline.as_str()) {
+            check = Check::Mismatch;
+            expected = Some(baseline);
+            VARIANTS_DIFFER.store(true, Ordering::Relaxed);
+        }
+    }
+
+    count_example(check == Check::Match);
+
+    let mut duration_str = format!("{}{}", format_duration(&stats), format_alloc(alloc));
+    if answer.solved().is_some() {
+        duration_str = match (check, &expected) {
+            (Check::Mismatch, Some(expected)) => {
+                format!(" {} expected {expected}{duration_str}", check.symbol())
+            }
+            _ => format!(" {}{duration_str}", check.symbol()),
+        };
+    }
+
+    print_result(&result.to_answer(), &part_str, &duration_str);
+    print_stats(&stats);
+
+    let status = match answer {
+        Answer::Solved(_) => Status::Solved,
+        Answer::Unsolved => Status::Unsolved,
+        Answer::Failed(_) => Status::Failed,
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    report::emit(&Record {
+        year,
+        day,
+        part: id.record.clone(),
+        status,
+        answer: answer.solved().map(ToString::to_string),
+        check,
+        message: match (&answer, check) {
+            (Answer::Failed(message), _) => Some(message.clone()),
+            (_, Check::Mismatch) => expected.map(|expected| format!("expected {expected}")),
+            _ => None,
+        },
+        nanos: stats.median.as_nanos(),
+        samples: stats.samples,
+    });
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    add_variant_row(
+        id,
+        answer.solved().map(ToString::to_string),
+        status,
+        Some(stats.median),
+    );
+
+    if let (Answer::Solved(answer), Some(part)) = (answer, id.submit) {
+        if let Some(Ok(output)) = submit_result(&answer, year, day, part) {
+            if aoc_cli::is_accepted(&output) && !input::is_custom() {
+                record_answer(year, day, part, &answer);
+            }
+        }
+    }
+}
+
+/// Ends the run of a solution binary, exiting with the exit code of [`run_solution`].
+///
+/// If stages were benchmarked, the benchmark settings are printed below the results. The `all`
+/// command prints them once for all days instead.
+pub fn finish(exit_code: i32) {
+    if BENCHED.load(Ordering::Relaxed) && env::var_os(REPORT_FILE_ENV).is_none() {
+        outln!("\n{ANSI_ITALIC}Benchmark: {}{ANSI_RESET}", bench_config());
+    }
+
+    if exit_code != 0 {
+        process::exit(exit_code);
     }
 }
 
+/// Mutes the text output of the runner, e.g. while `all` prints records instead.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Whether the text output of the runner is muted, see [`set_quiet`].
+pub(crate) fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Runs [`run_timed`] with panics caught and under the time limit, if one is configured.
+fn run_isolated<I, T>(func: fn(I) -> T, input: I, hook: impl Fn(&T) + Send + 'static) -> Outcome<T>
+where
+    I: Clone + Send + 'static,
+    T: Send + 'static,
+{
+    let outcome = match watchdog::time_limit() {
+        None => Some(unwind::catch(|| {
+            run_timed(func, input, |result| {
+                hook(result);
+                true
+            })
+        })),
+        // a stage that signals after it timed out is abandoned, its result is not shown.
+        Some(limit) => watchdog::run(limit, move |done| {
+            unwind::catch(|| {
+                run_timed(func, input, |result| {
+                    let in_time = done();
+                    if in_time {
+                        hook(result);
+                    }
+                    in_time
+                })
+            })
+        }),
+    };
+
+    match outcome {
+        Some(Ok((result, stats, alloc))) => Outcome::Finished(result, stats, alloc),
+        Some(Err(panic)) => Outcome::Panicked(panic),
+        None => Outcome::TimedOut(watchdog::time_limit().unwrap_or_default()),
+    }
+}
+
+/// Prints and reports a stage that did not finish, returning the status of its record.
+fn report_failure<T>(
+    outcome: &Outcome<T>,
+    label: &str,
+    year: Year,
+    day: Day,
+    part: &str,
+) -> Status {
+    let (status, message) = match outcome {
+        Outcome::Finished(..) => return Status::Solved,
+        Outcome::TimedOut(limit) => (Status::TimedOut, format!("timed out (> {limit:.1?})")),
+        Outcome::Panicked(panic) => {
+            PANICKED.store(true, Ordering::Relaxed);
+            (Status::Panicked, panic.to_string())
+        }
+    };
+
+    count_example(false);
+
+    out!("\r");
+    outln!("{label}: {message}             ");
+
+    report::emit(&Record {
+        year,
+        day,
+        part: part.into(),
+        status,
+        answer: None,
+        check: Check::Unknown,
+        message: Some(message),
+        nanos: match outcome {
+            Outcome::TimedOut(limit) => limit.as_nanos(),
+            _ => 0,
+        },
+        samples: 0,
+    });
+
+    status
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. with `--time`, the function is benched according to the [`BenchConfig`] (by default,
+///     approx. 1 second of execution time or 10 samples, whatever takes longer.)
+///
+/// Heap usage is only measured for the first execution, if allocation tracking is enabled.
+/// `hook` receives the first result and returns whether the run goes on, it isn't benchmarked
+/// otherwise.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
-
-    hook(&result);
+    hook: impl Fn(&T) -> bool,
+) -> (T, Stats, Option<AllocStats>) {
+    let ((result, base_time), alloc) = alloc::measure(|| {
+        let timer = Instant::now();
+        let result = func(input.clone());
+        (result, timer.elapsed())
+    });
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if !hook(&result) {
+        Stats::single(base_time)
+    } else if std::env::args().any(|x| x == "--time") {
+        bench(func, input, base_time)
     } else {
-        (base_time, 1)
+        Stats::single(base_time)
     };
 
-    (result, run.0, run.1)
+    (result, stats, alloc)
+}
+
+/// The answers that results are checked against: the expected answers of the current example, or
+/// the accepted answers of the day, read once from its answers file. Answers to an input given
+/// on the command line are unknown.
+fn answers(year: Year, day: Day) -> Answers {
+    EXPECTED
+        .lock()
+        .unwrap()
+        .get_or_insert_with(|| {
+            if input::is_custom() {
+                return Answers::default();
+            }
+
+            Answers::read(year, day).unwrap_or_else(|e| {
+                eprintln!("Ignoring answers of day {day}: {e}");
+                Answers::default()
+            })
+        })
+        .clone()
+}
+
+fn is_running_examples() -> bool {
+    EXAMPLES.lock().unwrap().is_some()
+}
+
+/// Counts a checked part while running examples.
+fn count_example(passed: bool) {
+    if let Some(tally) = EXAMPLES.lock().unwrap().as_mut() {
+        if passed {
+            tally.passed += 1;
+        } else {
+            tally.failed += 1;
+        }
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Records an answer that was accepted on submission, so that later runs can check against it.
+fn record_answer(year: Year, day: Day, part: u8, answer: &str) {
+    // re-read the file, it may have been edited while the solution ran.
+    let mut answers = Answers::read(year, day).unwrap_or_default();
+    answers.set(&part.to_string(), answer);
+
+    match answers.write(year, day) {
+        Ok(()) => outln!("🎄 Recorded the accepted answer of part {part}."),
+        Err(e) => eprintln!("Failed to record the accepted answer: {e}"),
+    }
+}
+
+/// The benchmark settings of this run, read once from the arguments and environment.
+fn bench_config() -> &'static BenchConfig {
+    static CONFIG: OnceLock<BenchConfig> = OnceLock::new();
+    CONFIG.get_or_init(BenchConfig::current)
+}
+
+/// Collects warm samples of a solution part. The first (cold) run has already been done by the
+/// caller and is only used to estimate the number of iterations.
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: Duration) -> Stats {
     let mut stdout = stdout();
+    let config = bench_config();
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+    out!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    BENCHED.store(true, Ordering::Relaxed);
+
+    let bench_iterations = config.iterations(base_time);
 
     let mut timers: Vec<Duration> = vec![];
 
+    // progress and trace output of the solution is muted until the guard is dropped, even on panic.
+    let _muted = OutputMute::new();
+
+    for _ in 0..config.warmup() {
+        func(input.clone());
+    }
+
     for _ in 0..bench_iterations {
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
@@ -69,69 +744,263 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    Stats::from_samples(base_time, &timers)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Mutes progress, trace output and visualisation for as long as it is alive.
+struct OutputMute;
+
+impl OutputMute {
+    fn new() -> Self {
+        progress::set_muted(true);
+        trace::set_muted(true);
+        visualize::set_muted(true);
+        Self
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+impl Drop for OutputMute {
+    fn drop(&mut self) {
+        progress::set_muted(false);
+        trace::set_muted(false);
+        visualize::set_muted(false);
+    }
+}
+
+/// Formats the timing of a part as `median ± standard deviation` of the warm samples.
+fn format_duration(stats: &Stats) -> String {
+    if stats.samples == 1 {
+        format!(" ({:.1?})", stats.median)
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({:.1?} ± {:.1?} @ {} samples)",
+            stats.median, stats.std_dev, stats.samples
+        )
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn format_alloc(alloc: Option<AllocStats>) -> String {
+    alloc.map_or_else(String::new, |alloc| format!(" [{alloc}]"))
+}
+
+/// Prints the remaining benchmark statistics below a part's result.
+fn print_stats(stats: &Stats) {
+    if stats.samples == 1 {
+        return;
+    }
+
+    let outliers = match stats.outliers {
+        1 => "1 outlier".to_string(),
+        n => format!("{n} outliers"),
+    };
+
+    outln!(
+        "  {ANSI_ITALIC}min {:.1?} · mean {:.1?} · p95 {:.1?} · {outliers} · cold {:.1?}{ANSI_RESET}",
+        stats.min, stats.mean, stats.p95, stats.cold
+    );
+}
+
+fn print_result(answer: &Answer, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+    match answer {
+        Answer::Solved(result) => {
+            if result.contains('\n') {
+                let str = match ocr::recognize(result) {
+                    Some(text) => format!("{part}: ▼ {ANSI_BOLD}{text}{ANSI_RESET}{duration_str}"),
+                    None => format!("{part}: ▼ {duration_str}"),
+                };
                 if is_intermediate_result {
-                    print!("{str}");
+                    out!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
+                    out!("\r");
+                    outln!("{str}");
+                    outln!("{result}");
                 }
             } else {
                 let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    out!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
+                    out!("\r");
+                    outln!("{str}");
                 }
             }
         }
-        None => {
+        Answer::Unsolved => {
+            if is_intermediate_result {
+                out!("{part}: ✖");
+            } else {
+                out!("\r");
+                outln!("{part}: ✖             ");
+            }
+        }
+        Answer::Failed(message) => {
+            let str = format!("{part}: ✖ {message}");
             if is_intermediate_result {
-                print!("{part}: ✖");
+                out!("{str}");
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                out!("\r");
+                outln!("{str}{duration_str}             ");
             }
         }
     }
 }
 
+/// The part requested with `--submit`, if any.
+fn requested_submit_part() -> Option<u8> {
+    let args: Vec<String> = env::args().collect();
+    let ix = args.iter().position(|x| x == "--submit")?;
+    args.get(ix + 1)?.parse().ok()
+}
+
+/// Checks that a day with `parts` parts has the part requested with `--submit`.
+fn check_submit_part(year: Year, day: Day, parts: u8, part: u8) -> Result<(), String> {
+    let event = year.event();
+    let count = |parts: u8| match parts {
+        1 => "1 part".to_string(),
+        n => format!("{n} parts"),
+    };
+
+    if part == 0 || part > event.parts {
+        Err(format!(
+            "There is no part {part} to submit, the {}s of {} have {}.",
+            event.day_label.to_lowercase(),
+            event.name,
+            count(event.parts)
+        ))
+    } else if part > parts {
+        Err(format!(
+            "{} {day} has {}, there is no part {part} to submit.",
+            event.day_label,
+            count(parts)
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// The implementations of the parts that run, read once from the arguments.
+fn variant_selection() -> &'static VariantSelection {
+    static SELECTION: OnceLock<VariantSelection> = OnceLock::new();
+    SELECTION.get_or_init(|| {
+        let args: Vec<String> = env::args().collect();
+        parse_variant_selection(&args)
+    })
+}
+
+fn parse_variant_selection(args: &[String]) -> VariantSelection {
+    if args.iter().any(|x| x == "--variants") {
+        return VariantSelection::All;
+    }
+
+    args.iter()
+        .position(|x| x == "--variant")
+        .and_then(|ix| args.get(ix + 1))
+        .map_or(VariantSelection::Default, |name| {
+            VariantSelection::One(name.clone())
+        })
+}
+
+/// The answer of the part whose variants are being compared, if it was solved.
+fn variant_baseline() -> Option<String> {
+    VARIANT_ROWS
+        .lock()
+        .unwrap()
+        .as_ref()?
+        .first()
+        .filter(|row| row.name == "default")?
+        .answer
+        .clone()
+}
+
+/// Adds the result of a part or variant to the table of [`print_variants`], if variants are
+/// being compared.
+fn add_variant_row(id: &PartId, answer: Option<String>, status: Status, median: Option<Duration>) {
+    if let Some(rows) = VARIANT_ROWS.lock().unwrap().as_mut() {
+        rows.push(VariantRow {
+            name: id.variant.unwrap_or("default").to_string(),
+            answer,
+            status,
+            median,
+        });
+    }
+}
+
+/// Prints a table of a part and its variants, see [`variant_table`].
+fn print_variants(part: u8, rows: &[VariantRow]) {
+    outln!("{ANSI_BOLD}Variants of part {part}:{ANSI_RESET}");
+    for line in variant_table(rows) {
+        outln!("  {line}");
+    }
+}
+
+/// The lines of a table of a part and its variants, with their answers compared to the part's and
+/// their timings relative to the fastest one.
+fn variant_table(rows: &[VariantRow]) -> Vec<String> {
+    let baseline = rows
+        .first()
+        .filter(|row| row.name == "default")
+        .and_then(|row| row.answer.as_deref());
+    let fastest = rows.iter().filter_map(|row| row.median).min();
+
+    let cells: Vec<[String; 4]> = rows
+        .iter()
+        .map(|row| {
+            let answer = match (&row.answer, baseline) {
+                (Some(answer), _) if row.name == "default" => answer.clone(),
+                (Some(answer), Some(baseline)) if answer == baseline => {
+                    format!("{answer} {}", Check::Match.symbol())
+                }
+                (Some(answer), Some(_)) => format!("{answer} {}", Check::Mismatch.symbol()),
+                (Some(answer), None) => format!("{answer} {}", Check::Unknown.symbol()),
+                (None, _) => row.status.to_string().replace('_', " "),
+            };
+
+            let (time, relative) = match (row.median, fastest) {
+                (Some(median), Some(fastest)) => (
+                    format!("{median:.1?}"),
+                    format!(
+                        "{:.1}×",
+                        median.as_secs_f64() / fastest.as_secs_f64().max(1e-9)
+                    ),
+                ),
+                _ => ("-".into(), "-".into()),
+            };
+
+            [row.name.clone(), answer, time, relative]
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..4)
+        .map(|col| {
+            cells
+                .iter()
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    cells
+        .iter()
+        .map(|row| {
+            let line: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{cell:<width$}"))
+                .collect();
+            line.join("   ").trim_end().to_string()
+        })
+        .collect()
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
 fn submit_result<T: Display>(
     result: T,
+    year: Year,
     day: Day,
     part: u8,
 ) -> Option<Result<Output, aoc_cli::AocCommandError>> {
@@ -157,11 +1026,117 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    if !year.event().aoc_cli {
+        eprintln!(
+            "Answers of {} can't be submitted via aoc-cli.",
+            year.event().name
+        );
+        process::exit(1);
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    outln!("Submitting result via aoc-cli...");
+    Some(aoc_cli::submit(year, day, part, &result.to_string()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        check_submit_part, parse_variant_selection, variant_table, VariantRow, VariantSelection,
+    };
+    use crate::template::report::Status;
+    use crate::{day, year};
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    fn row(name: &str, answer: Option<&str>, micros: Option<u64>) -> VariantRow {
+        VariantRow {
+            name: name.into(),
+            answer: answer.map(String::from),
+            status: match answer {
+                Some(_) => Status::Solved,
+                None => Status::TimedOut,
+            },
+            median: micros.map(Duration::from_micros),
+        }
+    }
+
+    #[test]
+    fn parses_the_variant_selection() {
+        let parse = |line| parse_variant_selection(&args(line));
+        assert_eq!(parse("solve 6"), VariantSelection::Default);
+        assert_eq!(parse("solve 6 --variant"), VariantSelection::Default);
+        assert_eq!(
+            parse("solve 6 --variant naive --time"),
+            VariantSelection::One("naive".into())
+        );
+        assert_eq!(parse("all --variants"), VariantSelection::All);
+        assert_eq!(
+            parse("all --variant naive --variants"),
+            VariantSelection::All
+        );
+    }
+
+    #[test]
+    fn marks_variants_by_the_answer_of_their_part() {
+        let table = variant_table(&[
+            row("default", Some("71503"), Some(2)),
+            row("naive", Some("71503"), Some(8)),
+            row("broken", Some("1"), Some(4)),
+            row("stuck", None, None),
+        ]);
+        assert_eq!(
+            table,
+            [
+                "default   71503       2.0µs   1.0×",
+                "naive     71503 ✓     8.0µs   4.0×",
+                "broken    1 ✗         4.0µs   2.0×",
+                "stuck     timed out   -       -",
+            ]
+        );
+    }
+
+    #[test]
+    fn marks_variants_as_unchecked_without_an_answer_of_their_part() {
+        let table = variant_table(&[
+            row("default", None, None),
+            row("naive", Some("42"), Some(1500)),
+            row("fast", Some("42"), Some(500)),
+        ]);
+        assert_eq!(
+            table,
+            [
+                "default   timed out   -         -",
+                "naive     42 ?        1.5ms     3.0×",
+                "fast      42 ?        500.0µs   1.0×",
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_the_part_to_submit() {
+        assert_eq!(check_submit_part(year!(2023), day!(8), 2, 2), Ok(()));
+        assert_eq!(
+            check_submit_part(year!(2023), day!(25), 1, 2),
+            Err("Day 25 has 1 part, there is no part 2 to submit.".into())
+        );
+        assert_eq!(
+            check_submit_part(year!(2023), day!(8), 2, 3),
+            Err("There is no part 3 to submit, the days of Advent of Code have 2 parts.".into())
+        );
+        assert_eq!(
+            check_submit_part("ec2024".parse().unwrap(), day!(3), 2, 3),
+            Err("Quest 03 has 2 parts, there is no part 3 to submit.".into())
+        );
+    }
 }
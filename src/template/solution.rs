@@ -0,0 +1,154 @@
+/// Input and return types of solution parts.
+///
+/// A part can return `Option<T>`, `Result<T, E>` or a plain answer like `u32` or `String`.
+/// The runner converts each of them into an [`Answer`]. Its input is the puzzle input as `&str`,
+/// or any [`FromInput`] type selected with the `input` option of [`solution!`](crate::solution).
+use std::fmt::Display;
+
+/// The answer of a part as shown and reported by the runner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Solved(String),
+    /// The part returned [`None`].
+    Unsolved,
+    /// The part returned an error, with its message.
+    Failed(String),
+}
+
+impl Answer {
+    /// The answer, if the part was solved.
+    #[must_use]
+    pub fn solved(&self) -> Option<&str> {
+        match self {
+            Answer::Solved(answer) => Some(answer),
+            _ => None,
+        }
+    }
+}
+
+/// A value that can be returned from a solution part.
+pub trait PartResult {
+    fn to_answer(&self) -> Answer;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    fn to_answer(&self) -> Answer {
+        match self {
+            Some(answer) => Answer::Solved(answer.to_string()),
+            None => Answer::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    fn to_answer(&self) -> Answer {
+        match self {
+            Ok(answer) => Answer::Solved(answer.to_string()),
+            Err(e) => Answer::Failed(e.to_string()),
+        }
+    }
+}
+
+macro_rules! impl_plain_part_result {
+    ($($t:ty),*) => {
+        $(
+            impl PartResult for $t {
+                fn to_answer(&self) -> Answer {
+                    Answer::Solved(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_plain_part_result!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, char, String, &str
+);
+
+/// A type that the puzzle input can be converted into, see the `input` option of
+/// [`solution!`](crate::solution). Implement it for your own types to parse the input into them.
+///
+/// A conversion may panic on malformed input, which the runner reports like a panicking parse
+/// stage.
+pub trait FromInput: Clone + Send + 'static {
+    fn from_input(input: &'static str) -> Self;
+}
+
+impl FromInput for &'static str {
+    fn from_input(input: &'static str) -> Self {
+        input
+    }
+}
+
+/// The raw bytes of the input.
+impl FromInput for &'static [u8] {
+    fn from_input(input: &'static str) -> Self {
+        input.as_bytes()
+    }
+}
+
+/// The lines of the input.
+impl FromInput for Vec<&'static str> {
+    fn from_input(input: &'static str) -> Self {
+        input.lines().collect()
+    }
+}
+
+/// The input as a grid of bytes, one row per line.
+impl FromInput for Vec<Vec<u8>> {
+    fn from_input(input: &'static str) -> Self {
+        input.lines().map(|line| line.as_bytes().to_vec()).collect()
+    }
+}
+
+/// The input as a grid of characters, one row per line.
+impl FromInput for Vec<Vec<char>> {
+    fn from_input(input: &'static str) -> Self {
+        input.lines().map(|line| line.chars().collect()).collect()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, FromInput, PartResult};
+    use crate::ParseError;
+
+    #[test]
+    fn converts_options() {
+        assert_eq!(Some(42).to_answer(), Answer::Solved("42".into()));
+        assert_eq!(None::<u32>.to_answer(), Answer::Unsolved);
+    }
+
+    #[test]
+    fn converts_results() {
+        let ok: Result<u64, ParseError> = Ok(42);
+        assert_eq!(ok.to_answer(), Answer::Solved("42".into()));
+
+        let err: Result<u64, ParseError> = "4x".parse::<u64>().map_err(ParseError::from);
+        assert_eq!(
+            err.to_answer(),
+            Answer::Failed("parse error: invalid digit found in string".into())
+        );
+    }
+
+    #[test]
+    fn converts_plain_values() {
+        assert_eq!(42_usize.to_answer(), Answer::Solved("42".into()));
+        assert_eq!("EFGHIJKL".to_answer().solved(), Some("EFGHIJKL"));
+    }
+
+    #[test]
+    fn converts_inputs() {
+        let input = "ab\ncd\n";
+        assert_eq!(<&[u8]>::from_input(input), b"ab\ncd\n");
+        assert_eq!(Vec::<&str>::from_input(input), vec!["ab", "cd"]);
+        assert_eq!(
+            Vec::<Vec<u8>>::from_input(input),
+            vec![b"ab".to_vec(), b"cd".to_vec()]
+        );
+        assert_eq!(
+            Vec::<Vec<char>>::from_input(input),
+            vec![vec!['a', 'b'], vec!['c', 'd']]
+        );
+    }
+}
@@ -0,0 +1,139 @@
+/// Summary statistics over the samples collected while benchmarking a solution part.
+use std::time::Duration;
+
+/// Timing statistics for one solution part.
+///
+/// The first (cold) run is kept separate from the warm samples, so a slow first call caused by
+/// page faults or lazy initialization does not skew the warm numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub cold: Duration,
+    pub samples: u128,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+    pub p95: Duration,
+    pub outliers: usize,
+}
+
+impl Stats {
+    /// Statistics for a part that was only executed once.
+    #[must_use]
+    pub fn single(cold: Duration) -> Self {
+        Self {
+            cold,
+            samples: 1,
+            min: cold,
+            median: cold,
+            mean: cold,
+            std_dev: Duration::ZERO,
+            p95: cold,
+            outliers: 0,
+        }
+    }
+
+    /// Computes statistics from the warm samples of a benchmark.
+    /// Falls back to [`Stats::single`] if no warm samples were collected.
+    #[must_use]
+    pub fn from_samples(cold: Duration, samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::single(cold);
+        }
+
+        let mut nanos: Vec<u128> = samples.iter().map(Duration::as_nanos).collect();
+        nanos.sort_unstable();
+
+        let count = nanos.len();
+        let mean = nanos.iter().sum::<u128>() as f64 / count as f64;
+        let variance = nanos
+            .iter()
+            .map(|x| (*x as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        // Tukey's fences: anything further than 1.5 IQR outside the quartiles is an outlier.
+        let q1 = percentile(&nanos, 25.0) as f64;
+        let q3 = percentile(&nanos, 75.0) as f64;
+        let iqr = q3 - q1;
+        let (lower, upper) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+        let outliers = nanos
+            .iter()
+            .filter(|x| (**x as f64) < lower || (**x as f64) > upper)
+            .count();
+
+        Self {
+            cold,
+            samples: count as u128,
+            min: from_nanos(nanos[0]),
+            median: from_nanos(percentile(&nanos, 50.0)),
+            mean: from_nanos_f64(mean),
+            std_dev: from_nanos_f64(variance.sqrt()),
+            p95: from_nanos(percentile(&nanos, 95.0)),
+            outliers,
+        }
+    }
+}
+
+/// Nearest-rank percentile of an already sorted, non-empty slice.
+fn percentile(sorted: &[u128], p: f64) -> u128 {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn from_nanos(nanos: u128) -> Duration {
+    #[allow(clippy::cast_possible_truncation)]
+    Duration::from_nanos(nanos as u64)
+}
+
+fn from_nanos_f64(nanos: f64) -> Duration {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Stats;
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn single_run() {
+        let stats = Stats::single(Duration::from_millis(3));
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+    }
+
+    #[test]
+    fn empty_samples_fall_back_to_cold_run() {
+        let stats = Stats::from_samples(Duration::from_millis(3), &[]);
+        assert_eq!(stats, Stats::single(Duration::from_millis(3)));
+    }
+
+    #[test]
+    fn summary_values() {
+        let stats = Stats::from_samples(
+            Duration::from_nanos(500),
+            &nanos(&[10, 12, 11, 13, 10, 11, 12, 10, 11, 100]),
+        );
+        assert_eq!(stats.cold, Duration::from_nanos(500));
+        assert_eq!(stats.samples, 10);
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.median, Duration::from_nanos(11));
+        assert_eq!(stats.mean, Duration::from_nanos(20));
+        assert_eq!(stats.p95, Duration::from_nanos(100));
+        assert_eq!(stats.outliers, 1);
+    }
+
+    #[test]
+    fn standard_deviation() {
+        let stats = Stats::from_samples(Duration::ZERO, &nanos(&[2, 4, 4, 4, 5, 5, 7, 9]));
+        assert_eq!(stats.mean, Duration::from_nanos(5));
+        assert_eq!(stats.std_dev, Duration::from_nanos(2));
+    }
+}
@@ -0,0 +1,147 @@
+/// Debug output of solutions that is kept apart from their answers.
+///
+/// Solutions log with [`trace!`](crate::trace), which does nothing unless the solution runs with
+/// `-v` (level 1), `-vv` (level 2) and so on. The output goes to stderr, or with `--log` to
+/// `data/<year>/logs/<day>.log`. It is muted while the runner benchmarks a part.
+use std::{
+    env,
+    fmt::Arguments,
+    fs::{self, File},
+    io::{stderr, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicU8, Ordering},
+        Mutex,
+    },
+};
+
+use crate::template::{get_data_path, watchdog};
+use crate::{Day, Year};
+
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+
+/// Set by the runner while it benchmarks a part.
+static MUTED: AtomicBool = AtomicBool::new(false);
+
+/// The log file, if output goes to a file instead of stderr.
+static LOG_FILE: Mutex<Option<File>> = Mutex::new(None);
+
+/// Logs a message if the verbosity is at least the given level, which defaults to 1.
+///
+/// ```ignore
+/// advent_of_code::trace!("parsed {} nodes", network.nodes.len());
+/// advent_of_code::trace!(2; "{network:?}");
+/// ```
+#[macro_export]
+macro_rules! trace {
+    ($level:literal; $($arg:tt)+) => {
+        if $crate::template::trace::is_enabled($level) {
+            $crate::template::trace::write(format_args!($($arg)+));
+        }
+    };
+    ($($arg:tt)+) => {
+        $crate::trace!(1; $($arg)+)
+    };
+}
+
+/// Sets up tracing for a solution binary from its `-v`/`--verbose` and `--log` arguments.
+pub fn init(year: Year, day: Day) {
+    let args: Vec<String> = env::args().skip(1).collect();
+    set_level(parse_level(&args));
+
+    if is_enabled(1) && args.iter().any(|arg| arg == "--log") {
+        let path = get_log_path(year, day);
+
+        let file = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| File::create(&path));
+
+        match file {
+            Ok(file) => *LOG_FILE.lock().unwrap() = Some(file),
+            Err(e) => eprintln!("Failed to create log file \"{}\": {e}", path.display()),
+        }
+    }
+}
+
+pub fn set_level(level: u8) {
+    LEVEL.store(level, Ordering::Relaxed);
+}
+
+/// Mutes or unmutes all trace output, e.g. while benchmarking.
+pub fn set_muted(muted: bool) {
+    MUTED.store(muted, Ordering::Relaxed);
+}
+
+#[must_use]
+#[inline]
+pub fn is_enabled(level: u8) -> bool {
+    level <= LEVEL.load(Ordering::Relaxed)
+        && !MUTED.load(Ordering::Relaxed)
+        && !watchdog::is_abandoned()
+}
+
+/// Writes a message to the log file or stderr. Use [`trace!`](crate::trace) instead.
+pub fn write(args: Arguments) {
+    let mut log_file = LOG_FILE.lock().unwrap();
+
+    let _ = match log_file.as_mut() {
+        Some(file) => writeln!(file, "{args}"),
+        None => writeln!(stderr(), "{args}"),
+    };
+}
+
+#[must_use]
+pub fn get_log_path(year: Year, day: Day) -> PathBuf {
+    get_data_path(year).join("logs").join(format!("{day}.log"))
+}
+
+/// Counts `-v` flags: `-v -v`, `-vv` and `--verbose --verbose` all mean level 2.
+fn parse_level(args: &[String]) -> u8 {
+    let level: usize = args.iter().filter_map(|arg| flag_level(arg)).sum();
+    u8::try_from(level).unwrap_or(u8::MAX)
+}
+
+/// The level that a single flag adds: 1 for `-v` and `--verbose`, the number of `v`s for `-vv`,
+/// `-vvvv` and so on. [`None`] for other arguments.
+#[must_use]
+pub fn flag_level(arg: &str) -> Option<usize> {
+    match arg {
+        "--verbose" => Some(1),
+        flag => match flag.strip_prefix('-') {
+            Some(vs) if !vs.is_empty() && vs.chars().all(|c| c == 'v') => Some(vs.len()),
+            _ => None,
+        },
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{flag_level, parse_level};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parses_levels() {
+        assert_eq!(parse_level(&args(&[])), 0);
+        assert_eq!(parse_level(&args(&["--time", "-v"])), 1);
+        assert_eq!(parse_level(&args(&["-vv"])), 2);
+        assert_eq!(parse_level(&args(&["-v", "--verbose", "-v"])), 3);
+        assert_eq!(parse_level(&args(&["-vvvvv"])), 5);
+    }
+
+    #[test]
+    fn counts_the_level_of_a_flag() {
+        assert_eq!(flag_level("-vvvv"), Some(4));
+        assert_eq!(flag_level("--verbose"), Some(1));
+        assert_eq!(flag_level("-vx"), None);
+        assert_eq!(flag_level("--vv"), None);
+    }
+
+    #[test]
+    fn ignores_other_flags() {
+        assert_eq!(parse_level(&args(&["--version", "-x", "-"])), 0);
+    }
+}
@@ -0,0 +1,123 @@
+/// Catches panics in solution code so that a failing part doesn't take down the whole run.
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+/// Exit code of a solution binary if any of its stages panicked.
+pub const EXIT_PANICKED: i32 = 3;
+
+/// The message and location of a caught panic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Panic {
+    pub message: String,
+    pub location: Option<String>,
+}
+
+impl Display for Panic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "panicked at {location}: {}", self.message),
+            None => write!(f, "panicked: {}", self.message),
+        }
+    }
+}
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<Panic>> = const { RefCell::new(None) };
+
+    /// Whether [`catch`] runs on this thread, so that the hook records its panics.
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Runs `func`, converting a panic into an [`Err`] with its message and location.
+///
+/// The default panic output is suppressed unless `RUST_BACKTRACE` is set, since the runner
+/// prints the panic in place of the part's result. Panics outside of `catch` are printed by the
+/// previous hook as usual.
+pub fn catch<T>(func: impl FnOnce() -> T) -> Result<T, Panic> {
+    install_hook();
+
+    let was_catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    CATCHING.set(was_catching);
+
+    result.map_err(|payload| {
+        LAST_PANIC
+            .with(|last| last.borrow_mut().take())
+            .unwrap_or_else(|| Panic {
+                message: payload_message(payload.as_ref()),
+                location: None,
+            })
+    })
+}
+
+fn install_hook() {
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.get() {
+                default_hook(info);
+                return;
+            }
+
+            LAST_PANIC.with(|last| {
+                *last.borrow_mut() = Some(Panic {
+                    message: payload_message(info.payload()),
+                    location: info.location().map(ToString::to_string),
+                });
+            });
+
+            if std::env::var_os("RUST_BACKTRACE").is_some() {
+                default_hook(info);
+            }
+        }));
+    });
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Box<dyn Any>".into()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{catch, LAST_PANIC};
+
+    #[test]
+    fn passes_through_results() {
+        assert_eq!(catch(|| 42), Ok(42));
+    }
+
+    #[test]
+    fn catches_panics() {
+        let line = line!() + 1;
+        let panic = catch(|| panic!("not found: {}", "XYZ")).unwrap_err();
+        assert_eq!(panic.message, "not found: XYZ");
+        assert_eq!(panic.location, Some(format!("{}:{line}:30", file!())));
+        assert!(panic.to_string().starts_with("panicked at "));
+    }
+
+    #[test]
+    fn catches_static_messages() {
+        let panic = catch(|| -> u32 { None.expect("missing first line") }).unwrap_err();
+        assert_eq!(panic.message, "missing first line");
+    }
+
+    #[test]
+    fn leaves_other_panics_to_the_previous_hook() {
+        catch(|| ()).unwrap();
+        let _ = std::panic::catch_unwind(|| panic!("outside of catch"));
+        assert!(LAST_PANIC.with(|last| last.borrow().is_none()));
+    }
+}
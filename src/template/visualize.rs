@@ -0,0 +1,699 @@
+/// Frame-by-frame visualisation of grid puzzles.
+///
+/// A solution pushes frames with [`push`]. Frames are only built and recorded if the solution
+/// runs with `--visualize <dir>` or `--replay`, and never while the runner benchmarks a part.
+/// At the end of the run, the runner writes the frames to `<dir>` as a PPM image sequence (or
+/// an animated GIF with `--gif`), or replays them in the terminal at `--fps` frames per second.
+///
+/// ```ignore
+/// use advent_of_code::template::visualize::{self, Frame};
+///
+/// visualize::push(|| Frame::from_chars(&grid).with_caption(format!("step {step}")));
+/// ```
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, stderr, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::template::{watchdog, ANSI_BOLD, ANSI_RESET};
+use crate::{Day, Year};
+
+/// Whether frames are recorded, set by [`init`].
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Set by the runner while it benchmarks a part.
+static MUTED: AtomicBool = AtomicBool::new(false);
+
+static FRAMES: Mutex<Vec<Frame>> = Mutex::new(vec![]);
+
+const DEFAULT_FPS: u32 = 10;
+
+/// Maximum width or height of an image in pixels, up to which cells are scaled up.
+const TARGET_IMAGE_SIZE: usize = 800;
+const MAX_SCALE: usize = 8;
+
+const BACKGROUND: Rgb = Rgb(0, 0, 0);
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    /// A character, drawn with a color derived from it.
+    Char(char),
+    /// A colored block.
+    Color(Rgb),
+}
+
+impl Cell {
+    fn color(self) -> Rgb {
+        match self {
+            Cell::Color(color) => color,
+            Cell::Char(c) => char_color(c),
+        }
+    }
+}
+
+/// A grid of cells with an optional caption.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+    caption: Option<String>,
+}
+
+impl Frame {
+    /// Creates a frame from rows of characters, e.g. a `Vec<Vec<char>>`. Shorter rows are padded
+    /// with spaces.
+    pub fn from_chars<R, C, T>(rows: R) -> Self
+    where
+        R: IntoIterator<Item = C>,
+        C: IntoIterator<Item = T>,
+        T: Borrow<char>,
+    {
+        let rows: Vec<Vec<char>> = rows
+            .into_iter()
+            .map(|row| row.into_iter().map(|c| *c.borrow()).collect())
+            .collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+        let cells = rows
+            .iter()
+            .flat_map(|row| (0..width).map(|x| Cell::Char(row.get(x).copied().unwrap_or(' '))))
+            .collect();
+
+        Self {
+            width,
+            height: rows.len(),
+            cells,
+            caption: None,
+        }
+    }
+
+    /// Creates a frame from the lines of a text, e.g. a grid formatted with `Display`.
+    pub fn from_text(text: &str) -> Self {
+        Self::from_chars(text.lines().map(str::chars))
+    }
+
+    /// Creates a frame of colored blocks.
+    pub fn from_colors(width: usize, height: usize, color: impl Fn(usize, usize) -> Rgb) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| Cell::Color(color(x, y)))
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+            caption: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Option<Cell> {
+        (x < self.width && y < self.height).then(|| self.cells[y * self.width + x])
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Visualisation settings of a solution run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    /// `--visualize <dir>`: folder the frames are written to.
+    pub output: Option<PathBuf>,
+    /// `--gif`: write an animated GIF instead of a PPM image sequence.
+    pub gif: bool,
+    /// `--replay`: replay the frames in the terminal.
+    pub replay: bool,
+    /// `--fps <n>`: frame rate of the replay and the GIF.
+    pub fps: Option<u32>,
+}
+
+impl Config {
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        Ok(Self {
+            output: args.opt_value_from_str("--visualize")?,
+            gif: args.contains("--gif"),
+            replay: args.contains("--replay"),
+            fps: args.opt_value_from_str("--fps")?,
+        })
+    }
+
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(output) = &self.output {
+            args.push("--visualize".into());
+            args.push(output.to_string_lossy().into_owned());
+        }
+
+        if self.gif {
+            args.push("--gif".into());
+        }
+
+        if self.replay {
+            args.push("--replay".into());
+        }
+
+        if let Some(fps) = self.fps {
+            args.push("--fps".into());
+            args.push(fps.to_string());
+        }
+
+        args
+    }
+
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.output.is_some() || self.replay
+    }
+
+    fn frame_delay(&self) -> Duration {
+        Duration::from_secs(1) / self.fps.unwrap_or(DEFAULT_FPS).max(1)
+    }
+}
+
+/// Enables recording of frames if the running solution binary was asked to visualize.
+pub fn init() {
+    let config = current_config();
+    ENABLED.store(config.is_enabled(), Ordering::Relaxed);
+}
+
+/// Mutes or unmutes the recording of frames, e.g. while benchmarking.
+pub fn set_muted(muted: bool) {
+    MUTED.store(muted, Ordering::Relaxed);
+}
+
+/// Whether frames pushed now are recorded.
+#[must_use]
+#[inline]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed) && !MUTED.load(Ordering::Relaxed) && !watchdog::is_abandoned()
+}
+
+/// Records a frame. `frame` is only called if visualisation is enabled, so building a frame
+/// costs nothing otherwise.
+#[inline]
+pub fn push(frame: impl FnOnce() -> Frame) {
+    if is_enabled() {
+        FRAMES.lock().unwrap().push(frame());
+    }
+}
+
+/// Writes or replays the recorded frames of a run.
+pub fn output(year: Year, day: Day) {
+    let frames = std::mem::take(&mut *FRAMES.lock().unwrap());
+    if frames.is_empty() {
+        return;
+    }
+
+    let config = current_config();
+
+    if config.replay {
+        if let Err(e) = replay(&frames, config.frame_delay()) {
+            eprintln!("Failed to replay frames: {e}");
+        }
+    }
+
+    if let Some(dir) = &config.output {
+        let result = if config.gif {
+            let path = dir.join(format!("{year}-{day}.gif"));
+            write_gif(&path, &frames, config.frame_delay()).map(|()| path)
+        } else {
+            write_ppm_sequence(dir, &format!("{year}-{day}"), &frames).map(|()| dir.clone())
+        };
+
+        match result {
+            Ok(path) => eprintln!(
+                "🎄 Wrote {} frames to \"{}\".",
+                frames.len(),
+                path.display()
+            ),
+            Err(e) => eprintln!("Failed to write frames to \"{}\": {e}", dir.display()),
+        }
+    }
+}
+
+fn current_config() -> Config {
+    Config::from_args(&mut pico_args::Arguments::from_env()).unwrap_or_else(|e| {
+        eprintln!("Ignoring visualisation arguments: {e}");
+        Config::default()
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn replay(frames: &[Frame], delay: Duration) -> io::Result<()> {
+    let mut out = stderr().lock();
+
+    for frame in frames {
+        // move the cursor home and clear the screen.
+        let mut screen = String::from("\x1b[H\x1b[2J");
+
+        for y in 0..frame.height {
+            for x in 0..frame.width {
+                let cell = frame.cell(x, y).unwrap_or(Cell::Char(' '));
+                let Rgb(r, g, b) = cell.color();
+                let text = match cell {
+                    Cell::Char(c) => c.to_string(),
+                    Cell::Color(_) => "██".into(),
+                };
+                screen.push_str(&format!("\x1b[38;2;{r};{g};{b}m{text}"));
+            }
+            screen.push_str(ANSI_RESET);
+            screen.push('\n');
+        }
+
+        if let Some(caption) = &frame.caption {
+            screen.push_str(&format!("{ANSI_BOLD}{caption}{ANSI_RESET}\n"));
+        }
+
+        out.write_all(screen.as_bytes())?;
+        out.flush()?;
+        thread::sleep(delay);
+    }
+
+    Ok(())
+}
+
+fn char_color(c: char) -> Rgb {
+    const PALETTE: [Rgb; 8] = [
+        Rgb(231, 76, 60),
+        Rgb(46, 204, 113),
+        Rgb(52, 152, 219),
+        Rgb(241, 196, 15),
+        Rgb(155, 89, 182),
+        Rgb(26, 188, 156),
+        Rgb(230, 126, 34),
+        Rgb(236, 240, 241),
+    ];
+
+    match c {
+        ' ' | '.' => Rgb(24, 24, 32),
+        '#' => Rgb(220, 220, 220),
+        '0'..='9' => {
+            let level = (c as u8 - b'0') * 25;
+            Rgb(level, 80 + level / 2, 255 - level)
+        }
+        c => PALETTE[c as usize % PALETTE.len()],
+    }
+}
+
+/// The size of a cell in pixels, so that the frames are roughly [`TARGET_IMAGE_SIZE`] wide.
+fn scale(frames: &[Frame]) -> usize {
+    let size = frames
+        .iter()
+        .map(|f| f.width.max(f.height))
+        .max()
+        .unwrap_or(1)
+        .max(1);
+
+    (TARGET_IMAGE_SIZE / size).clamp(1, MAX_SCALE)
+}
+
+/// Renders a frame to pixels, padded to the given size in cells.
+fn render(frame: &Frame, width: usize, height: usize, scale: usize) -> Vec<Rgb> {
+    let mut pixels = Vec::with_capacity(width * height * scale * scale);
+
+    for y in 0..height * scale {
+        for x in 0..width * scale {
+            let color = frame
+                .cell(x / scale, y / scale)
+                .map_or(BACKGROUND, Cell::color);
+            pixels.push(color);
+        }
+    }
+
+    pixels
+}
+
+fn write_ppm_sequence(dir: &Path, name: &str, frames: &[Frame]) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let scale = scale(frames);
+
+    for (ix, frame) in frames.iter().enumerate() {
+        let path = dir.join(format!("{name}-{:05}.ppm", ix + 1));
+        let mut file = BufWriter::new(File::create(path)?);
+
+        writeln!(file, "P6")?;
+        if let Some(caption) = &frame.caption {
+            writeln!(file, "# {}", caption.replace('\n', " "))?;
+        }
+        write!(
+            file,
+            "{} {}\n255\n",
+            frame.width * scale,
+            frame.height * scale
+        )?;
+
+        for Rgb(r, g, b) in render(frame, frame.width, frame.height, scale) {
+            file.write_all(&[r, g, b])?;
+        }
+
+        file.flush()?;
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn write_gif(path: &Path, frames: &[Frame], delay: Duration) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = BufWriter::new(File::create(path)?);
+    file.write_all(&encode_gif(frames, delay))?;
+    file.flush()
+}
+
+/// Encodes frames as an animated, looping GIF. Frames are padded to the size of the largest one.
+fn encode_gif(frames: &[Frame], delay: Duration) -> Vec<u8> {
+    let scale = scale(frames);
+    let width = frames.iter().map(|f| f.width).max().unwrap_or(0).max(1);
+    let height = frames.iter().map(|f| f.height).max().unwrap_or(0).max(1);
+
+    let images: Vec<Vec<Rgb>> = frames
+        .iter()
+        .map(|frame| render(frame, width, height, scale))
+        .collect();
+
+    let (palette, index) = build_palette(&images);
+
+    // the color table has 2^bits entries.
+    let bits = (usize::BITS - (palette.len() - 1).leading_zeros()).max(1);
+    #[allow(clippy::cast_possible_truncation)]
+    let bits = bits as u8;
+
+    #[allow(clippy::cast_possible_truncation)]
+    let (pixel_width, pixel_height) = ((width * scale) as u16, (height * scale) as u16);
+
+    let mut gif = b"GIF89a".to_vec();
+
+    // logical screen descriptor with a global color table.
+    gif.extend(pixel_width.to_le_bytes());
+    gif.extend(pixel_height.to_le_bytes());
+    gif.extend([0xF0 | (bits - 1), 0, 0]);
+
+    for i in 0..1 << bits {
+        let Rgb(r, g, b) = palette.get(i).copied().unwrap_or(BACKGROUND);
+        gif.extend([r, g, b]);
+    }
+
+    // loop forever.
+    gif.extend([0x21, 0xFF, 0x0B]);
+    gif.extend(b"NETSCAPE2.0");
+    gif.extend([0x03, 0x01, 0x00, 0x00, 0x00]);
+
+    #[allow(clippy::cast_possible_truncation)]
+    let delay = (delay.as_millis() / 10).clamp(1, u128::from(u16::MAX)) as u16;
+
+    for (frame, image) in frames.iter().zip(&images) {
+        if let Some(caption) = &frame.caption {
+            gif.extend([0x21, 0xFE]);
+            push_sub_blocks(&mut gif, caption.as_bytes());
+        }
+
+        // graphic control extension with the frame delay.
+        gif.extend([0x21, 0xF9, 0x04, 0x04]);
+        gif.extend(delay.to_le_bytes());
+        gif.extend([0x00, 0x00]);
+
+        // image descriptor covering the whole screen.
+        gif.push(0x2C);
+        gif.extend([0, 0, 0, 0]);
+        gif.extend(pixel_width.to_le_bytes());
+        gif.extend(pixel_height.to_le_bytes());
+        gif.push(0);
+
+        let indices: Vec<u8> = image
+            .iter()
+            .map(|color| index[&quantize_if(color, &index)])
+            .collect();
+        let min_code_size = bits.max(2);
+        gif.push(min_code_size);
+        push_sub_blocks(&mut gif, &lzw_encode(&indices, min_code_size));
+    }
+
+    gif.push(0x3B);
+    gif
+}
+
+/// Collects up to 256 colors. If the images use more, they are reduced to a 6x6x6 color cube.
+fn build_palette(images: &[Vec<Rgb>]) -> (Vec<Rgb>, HashMap<Rgb, u8>) {
+    let mut palette: Vec<Rgb> = vec![BACKGROUND];
+    let mut index: HashMap<Rgb, u8> = HashMap::from([(BACKGROUND, 0)]);
+
+    for color in images.iter().flatten() {
+        if !index.contains_key(color) {
+            if palette.len() == 256 {
+                return cube_palette();
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            index.insert(*color, palette.len() as u8);
+            palette.push(*color);
+        }
+    }
+
+    (palette, index)
+}
+
+fn cube_palette() -> (Vec<Rgb>, HashMap<Rgb, u8>) {
+    const LEVELS: [u8; 6] = [0, 51, 102, 153, 204, 255];
+
+    let palette: Vec<Rgb> = LEVELS
+        .iter()
+        .flat_map(|&r| {
+            LEVELS
+                .iter()
+                .flat_map(move |&g| LEVELS.iter().map(move |&b| Rgb(r, g, b)))
+        })
+        .collect();
+
+    #[allow(clippy::cast_possible_truncation)]
+    let index = palette
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (*c, i as u8))
+        .collect();
+    (palette, index)
+}
+
+/// Maps a color to the nearest cube color, if the palette doesn't contain it.
+fn quantize_if(color: &Rgb, index: &HashMap<Rgb, u8>) -> Rgb {
+    if index.contains_key(color) {
+        return *color;
+    }
+
+    let level = |c: u8| ((u16::from(c) + 25) / 51 * 51) as u8;
+    Rgb(level(color.0), level(color.1), level(color.2))
+}
+
+fn push_sub_blocks(out: &mut Vec<u8>, data: &[u8]) {
+    for chunk in data.chunks(255) {
+        #[allow(clippy::cast_possible_truncation)]
+        out.push(chunk.len() as u8);
+        out.extend(chunk);
+    }
+    out.push(0);
+}
+
+/// Compresses color indices with the variable-length LZW scheme of GIF.
+fn lzw_encode(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    const MAX_CODE: u16 = 4096;
+
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    let mut out = Vec::new();
+    let mut buffer: u32 = 0;
+    let mut buffered = 0;
+
+    let mut emit = |code: u16, size: u8, out: &mut Vec<u8>| {
+        buffer |= u32::from(code) << buffered;
+        buffered += size;
+        while buffered >= 8 {
+            #[allow(clippy::cast_possible_truncation)]
+            out.push(buffer as u8);
+            buffer >>= 8;
+            buffered -= 8;
+        }
+    };
+
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut code_size = min_code_size + 1;
+    let mut next = end + 1;
+
+    emit(clear, code_size, &mut out);
+
+    let mut prefix: Option<u16> = None;
+
+    for &k in indices {
+        let Some(current) = prefix else {
+            prefix = Some(u16::from(k));
+            continue;
+        };
+
+        if let Some(&code) = table.get(&(current, k)) {
+            prefix = Some(code);
+            continue;
+        }
+
+        emit(current, code_size, &mut out);
+
+        if next < MAX_CODE {
+            table.insert((current, k), next);
+            next += 1;
+            // the decoder widens its codes one entry later than the encoder adds them.
+            if next > 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+        } else {
+            emit(clear, code_size, &mut out);
+            table.clear();
+            code_size = min_code_size + 1;
+            next = end + 1;
+        }
+
+        prefix = Some(u16::from(k));
+    }
+
+    if let Some(current) = prefix {
+        emit(current, code_size, &mut out);
+    }
+    emit(end, code_size, &mut out);
+
+    if buffered > 0 {
+        #[allow(clippy::cast_possible_truncation)]
+        out.push(buffer as u8);
+    }
+
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{encode_gif, lzw_encode, Cell, Frame, Rgb};
+    use std::time::Duration;
+
+    /// Decodes a GIF LZW stream, to check the encoder against.
+    fn lzw_decode(data: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+
+        let mut table: Vec<Vec<u8>> = vec![];
+        let reset = |table: &mut Vec<Vec<u8>>| {
+            *table = (0..clear).map(|i| vec![i as u8]).collect();
+            table.push(vec![]);
+            table.push(vec![]);
+        };
+        reset(&mut table);
+
+        let mut code_size = min_code_size + 1;
+        let mut out = vec![];
+        let mut prev: Option<usize> = None;
+        let (mut buffer, mut buffered, mut pos) = (0u32, 0u8, 0);
+
+        loop {
+            while buffered < code_size {
+                buffer |= u32::from(data[pos]) << buffered;
+                pos += 1;
+                buffered += 8;
+            }
+            let code = (buffer & ((1 << code_size) - 1)) as usize;
+            buffer >>= code_size;
+            buffered -= code_size;
+
+            if code == clear {
+                reset(&mut table);
+                code_size = min_code_size + 1;
+                prev = None;
+                continue;
+            }
+            if code == end {
+                return out;
+            }
+
+            let entry = match (table.get(code), prev) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(prev)) => {
+                    let mut entry = table[prev].clone();
+                    entry.push(table[prev][0]);
+                    entry
+                }
+                (None, None) => panic!("invalid code {code}"),
+            };
+
+            if let Some(prev) = prev {
+                if table.len() < 4096 {
+                    let mut new = table[prev].clone();
+                    new.push(entry[0]);
+                    table.push(new);
+                    if table.len() == 1 << code_size && code_size < 12 {
+                        code_size += 1;
+                    }
+                }
+            }
+
+            out.extend(&entry);
+            prev = Some(code);
+        }
+    }
+
+    #[test]
+    fn lzw_round_trips() {
+        let data: Vec<u8> = (0..20_000u32)
+            .map(|i| ((i * 7 + i / 13) % 5) as u8)
+            .collect();
+        assert_eq!(lzw_decode(&lzw_encode(&data, 3), 3), data);
+
+        let noise: Vec<u8> = (0..50_000u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+            .collect();
+        assert_eq!(lzw_decode(&lzw_encode(&noise, 8), 8), noise);
+    }
+
+    #[test]
+    fn builds_frames() {
+        let frame = Frame::from_text("#.\n.").with_caption("step 1");
+        assert_eq!(frame.width, 2);
+        assert_eq!(frame.height, 2);
+        assert_eq!(frame.cell(1, 1), Some(Cell::Char(' ')));
+        assert_eq!(frame.cell(2, 0), None);
+
+        let frame = Frame::from_colors(3, 1, |x, _| Rgb(x as u8, 0, 0));
+        assert_eq!(frame.cell(2, 0), Some(Cell::Color(Rgb(2, 0, 0))));
+    }
+
+    #[test]
+    fn encodes_gif() {
+        let frames = [
+            Frame::from_text("#..\n.#.").with_caption("1"),
+            Frame::from_chars([vec!['.', '.', '#']]),
+        ];
+        let gif = encode_gif(&frames, Duration::from_millis(100));
+        assert!(gif.starts_with(b"GIF89a"));
+        assert_eq!(gif.last(), Some(&0x3B));
+    }
+}
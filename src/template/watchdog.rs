@@ -0,0 +1,208 @@
+/// Time limits for solution parts.
+///
+/// A limited stage runs on its own thread. If it does not produce its first result in time, the
+/// runner reports it as timed out and moves on. Threads can't be stopped, so a timed-out stage
+/// keeps running, along with the input and parsed values it holds, until the process exits. It is
+/// abandoned though: its result is neither printed nor benchmarked, and its progress, trace and
+/// visualisation output is muted, see [`is_abandoned`].
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+static TIME_LIMIT_OVERRIDE: Mutex<Option<Duration>> = Mutex::new(None);
+
+/// The states of a limited stage, shared between the runner and the stage's thread.
+const PENDING: u8 = 0;
+const FINISHED: u8 = 1;
+const ABANDONED: u8 = 2;
+
+thread_local! {
+    /// The state of the limited stage that runs on this thread, if any.
+    static STAGE: RefCell<Option<Arc<AtomicU8>>> = const { RefCell::new(None) };
+}
+
+/// Parses a human-readable duration such as `250us`, `500ms`, `10s`, `1.5m` or `2h`.
+/// A number without unit is interpreted as seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("invalid duration `{s}`, expecting e.g. `10s` or `500ms`"))?;
+
+    let seconds = match unit.trim() {
+        "ns" => value / 1e9,
+        "us" | "µs" => value / 1e6,
+        "ms" => value / 1000.0,
+        "" | "s" => value,
+        "m" | "min" => value * 60.0,
+        "h" => value * 3600.0,
+        unit => return Err(format!("unknown duration unit `{unit}` in `{s}`")),
+    };
+
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+/// Overrides the time limit passed on the command line, e.g. for a day that is known to be slow.
+pub fn set_time_limit(limit: Duration) {
+    *TIME_LIMIT_OVERRIDE.lock().unwrap() = Some(limit);
+}
+
+/// Removes the override of [`set_time_limit`], before running another day in the same process.
+pub fn reset_time_limit() {
+    *TIME_LIMIT_OVERRIDE.lock().unwrap() = None;
+}
+
+/// The time limit for a single stage: the override set by the solution if any, otherwise the
+/// value of the `--timeout` argument.
+#[must_use]
+pub fn time_limit() -> Option<Duration> {
+    if let Some(limit) = *TIME_LIMIT_OVERRIDE.lock().unwrap() {
+        return Some(limit);
+    }
+
+    let args: Vec<String> = std::env::args().collect();
+    let value = args
+        .iter()
+        .position(|x| x == "--timeout")
+        .and_then(|ix| args.get(ix + 1))?;
+
+    match parse_duration(value) {
+        Ok(limit) => Some(limit),
+        Err(e) => {
+            eprintln!("Ignoring --timeout: {e}");
+            None
+        }
+    }
+}
+
+/// Whether the current thread runs a stage that timed out, whose output is no longer wanted.
+#[must_use]
+pub fn is_abandoned() -> bool {
+    STAGE.with(|stage| {
+        stage
+            .borrow()
+            .as_ref()
+            .is_some_and(|state| state.load(Ordering::SeqCst) == ABANDONED)
+    })
+}
+
+/// Runs `func` on a separate thread. `func` receives a callback that it must call once its
+/// result is ready; if that does not happen within `limit`, [`None`] is returned and the thread
+/// is abandoned. The callback returns whether the result is still awaited, `false` once the
+/// thread has been abandoned, in which case `func` should return without doing anything else.
+/// Anything `func` does after signalling in time (e.g. benchmarking) is not subject to the limit.
+pub fn run<T: Send + 'static>(
+    limit: Duration,
+    func: impl FnOnce(&dyn Fn() -> bool) -> T + Send + 'static,
+) -> Option<T> {
+    let (tx, rx) = mpsc::channel();
+    let state = Arc::new(AtomicU8::new(PENDING));
+    let thread_state = Arc::clone(&state);
+
+    let handle = thread::spawn(move || {
+        STAGE.with(|stage| *stage.borrow_mut() = Some(Arc::clone(&thread_state)));
+
+        let signal = || {
+            let in_time = thread_state
+                .compare_exchange(PENDING, FINISHED, Ordering::SeqCst, Ordering::SeqCst)
+                .map_or_else(|current| current == FINISHED, |_| true);
+            let _ = tx.send(());
+            in_time
+        };
+        func(&signal)
+    });
+
+    // the stage may signal right as the limit passes, whichever comes first decides.
+    let timed_out = rx.recv_timeout(limit) == Err(mpsc::RecvTimeoutError::Timeout)
+        && state
+            .compare_exchange(PENDING, ABANDONED, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok();
+
+    if timed_out {
+        return None;
+    }
+
+    // the thread signalled completion, or ended without doing so.
+    match handle.join() {
+        Ok(result) => Some(result),
+        Err(panic) => std::panic::resume_unwind(panic),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_abandoned, parse_duration, run};
+    use std::{sync::mpsc, thread, time::Duration};
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("250ns"), Ok(Duration::from_nanos(250)));
+        assert_eq!(parse_duration("500us"), Ok(Duration::from_micros(500)));
+        assert_eq!(parse_duration("1.5µs"), Ok(Duration::from_nanos(1500)));
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("10s"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_duration("10"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("10 parsecs").is_err());
+    }
+
+    #[test]
+    fn finishes_in_time() {
+        let result = run(Duration::from_secs(5), |done| {
+            done();
+            42
+        });
+        assert_eq!(result, Some(42));
+    }
+
+    #[test]
+    fn work_after_signal_is_not_limited() {
+        let result = run(Duration::from_millis(50), |done| {
+            done();
+            std::thread::sleep(Duration::from_millis(200));
+            42
+        });
+        assert_eq!(result, Some(42));
+    }
+
+    #[test]
+    fn times_out() {
+        let result = run(Duration::from_millis(50), |done| {
+            std::thread::sleep(Duration::from_secs(2));
+            done();
+            42
+        });
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn abandons_stages_that_time_out() {
+        let (tx, rx) = mpsc::channel();
+        let result = run(Duration::from_millis(50), move |done| {
+            thread::sleep(Duration::from_millis(200));
+            tx.send((done(), is_abandoned())).unwrap();
+        });
+        assert_eq!(result, None);
+        assert_eq!(rx.recv().unwrap(), (false, true));
+        assert!(!is_abandoned());
+    }
+}
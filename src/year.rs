@@ -0,0 +1,262 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::{all_days, Day, Event, ADVENT_OF_CODE};
+
+/// A year of an [`Event`], e.g. `2023` of Advent of Code or `ec2024` of Everybody Codes.
+///
+/// The year displays with the prefix of its event, which also names its solution files and data
+/// directory. The number of days comes from the event.
+///
+/// ```
+/// # use advent_of_code::{Day, Year};
+/// let year = Year::new(2025).unwrap();
+/// assert_eq!(year.day_count(), 12);
+/// assert!(!year.has_day(Day::new(13).unwrap()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year {
+    prefix: &'static str,
+    number: u16,
+}
+
+impl Year {
+    /// Creates a [`Year`] of Advent of Code from the provided value if it's 2015 or later,
+    /// returns [`None`] otherwise. See [`Event::year`] for other events.
+    pub fn new(year: u16) -> Option<Self> {
+        ADVENT_OF_CODE.year(year)
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(prefix: &'static str, year: u16) -> Self {
+        Self {
+            prefix,
+            number: year,
+        }
+    }
+
+    /// Converts the [`Year`] into an [`u16`], without the prefix of its event.
+    pub const fn into_inner(self) -> u16 {
+        self.number
+    }
+
+    /// The prefix of this year's event, e.g. `ec` for `ec2024`.
+    pub const fn prefix(self) -> &'static str {
+        self.prefix
+    }
+
+    /// The event of this year.
+    pub const fn event(self) -> &'static Event {
+        match Event::find(self.prefix) {
+            Some(event) => event,
+            None => panic!("the event of a year is listed in `EVENTS`"),
+        }
+    }
+
+    /// The number of days of this year's event.
+    pub const fn day_count(self) -> u8 {
+        self.event().day_count(self.number)
+    }
+
+    /// Whether the day is part of this year's event.
+    pub fn has_day(self, day: Day) -> bool {
+        day.into_inner() <= self.day_count()
+    }
+
+    /// Returns the day if it is part of this year's event, an error otherwise.
+    pub fn check_day(self, day: Day) -> Result<Day, DayNotInYearError> {
+        if self.has_day(day) {
+            Ok(day)
+        } else {
+            Err(DayNotInYearError { year: self, day })
+        }
+    }
+
+    /// An iterator over the days of this year's event.
+    pub fn days(self) -> impl Iterator<Item = Day> {
+        all_days().take(self.day_count().into())
+    }
+
+    /// The year set in the `AOC_YEAR` environment variable, if it is valid.
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    // Not part of the public API
+    /// Reads the year of a solution from its file name `<prefix><year>-<day>.rs`, see `solution!`.
+    #[doc(hidden)]
+    pub const fn __from_solution_path(path: &str) -> Self {
+        const MESSAGE: &str = "solution files must be named `<year>-<day>.rs`, e.g. `2023-08.rs`";
+
+        let bytes = path.as_bytes();
+
+        let mut start = bytes.len();
+        while start > 0 && bytes[start - 1] != b'/' && bytes[start - 1] != b'\\' {
+            start -= 1;
+        }
+
+        // `2023-08.rs`, after the prefix of the event.
+        let suffix_len = 10;
+        assert!(bytes.len() - start >= suffix_len, "{}", MESSAGE);
+        let digits = bytes.len() - suffix_len;
+        assert!(bytes[digits + 4] == b'-', "{}", MESSAGE);
+
+        let mut year = 0;
+        let mut ix = digits;
+        while ix < digits + 4 {
+            let digit = bytes[ix];
+            assert!(digit.is_ascii_digit(), "{}", MESSAGE);
+            year = year * 10 + (digit - b'0') as u16;
+            ix += 1;
+        }
+
+        let (head, _) = path.split_at(digits);
+        let (_, prefix) = head.split_at(start);
+        let Some(event) = Event::find(prefix) else {
+            panic!("unknown event prefix in the solution file name, see `EVENTS`");
+        };
+
+        assert!(
+            year >= event.first_year,
+            "the event did not take place in this year"
+        );
+        Self::__new_unchecked(event.prefix, year)
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.prefix, self.number)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.find(|c: char| c.is_ascii_digit()).unwrap_or(s.len());
+        let (prefix, year) = s.split_at(digits);
+        let event = Event::find(prefix).ok_or(YearFromStrError)?;
+        let year = year.parse().map_err(|_| YearFromStrError)?;
+        event.year(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of an event, e.g. `2023` or `ec2024`")
+    }
+}
+
+/// An error which is returned for a day that is not part of a year's event.
+#[derive(Debug)]
+pub struct DayNotInYearError {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl Error for DayNotInYearError {}
+
+impl Display for DayNotInYearError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = self.year.event().day_label.to_lowercase();
+        write!(
+            f,
+            "{label} {} is not part of {}, which has {} {label}s",
+            self.day.into_inner(),
+            self.year,
+            self.year.day_count()
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] of Advent of Code in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= $crate::ADVENT_OF_CODE.first_year,
+            concat!("invalid year `", $year, "`, expecting 2015 or later"),
+        );
+        $crate::Year::__new_unchecked("", $year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+    use crate::{day, EVERYBODY_CODES};
+
+    #[test]
+    fn counts_days() {
+        assert_eq!(year!(2015).days().count(), 25);
+        assert_eq!(year!(2024).days().last(), Some(day!(25)));
+        assert_eq!(year!(2025).days().count(), 12);
+    }
+
+    #[test]
+    fn checks_days() {
+        assert!(year!(2023).check_day(day!(25)).is_ok());
+        assert_eq!(
+            year!(2025).check_day(day!(13)).unwrap_err().to_string(),
+            "day 13 is not part of 2025, which has 12 days"
+        );
+    }
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2023".parse::<Year>().unwrap(), year!(2023));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("23".parse::<Year>().is_err());
+
+        let year = "ec2024".parse::<Year>().unwrap();
+        assert_eq!(year.event(), &EVERYBODY_CODES);
+        assert_eq!(year.to_string(), "ec2024");
+        assert!("ec2023".parse::<Year>().is_err());
+        assert!("xy2024".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn counts_days_of_other_events() {
+        let year = "ec2024".parse::<Year>().unwrap();
+        assert_eq!(year.days().count(), 20);
+        assert!(year.check_day(day!(21)).is_err());
+    }
+
+    #[test]
+    fn reads_solution_paths() {
+        assert_eq!(
+            Year::__from_solution_path("src/bin/2023-08.rs"),
+            year!(2023)
+        );
+        assert_eq!(
+            Year::__from_solution_path("/home/me/aoc/src/bin/2025-12.rs"),
+            year!(2025)
+        );
+        assert_eq!(
+            Year::__from_solution_path("src/bin/ec2024-03.rs"),
+            "ec2024".parse().unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "solution files must be named")]
+    fn rejects_other_solution_paths() {
+        Year::__from_solution_path("src/bin/08.rs");
+    }
+}